@@ -5,6 +5,8 @@
 use led_star::{config, star::Star};
 use panic_halt as _;
 
+#[cfg(feature = "clock")]
+mod rtc;
 mod ws2812;
 use ws2812::Ws2812;
 
@@ -24,7 +26,19 @@ fn main() -> ! {
 
     // Create star with layout and pattern from config
     let layout = config::layout();
+    #[cfg(not(feature = "clock"))]
     let pattern = config::pattern();
+    // Show the time from a DS3231 real-time clock on the I2C pins (A4/A5)
+    #[cfg(feature = "clock")]
+    let pattern = {
+        let i2c = arduino_hal::I2c::new(
+            dp.TWI,
+            pins.a4.into_pull_up_input(),
+            pins.a5.into_pull_up_input(),
+            50000,
+        );
+        config::clock_pattern(rtc::Ds3231::new(i2c))
+    };
     let mut star = Star::new(layout, pattern);
 
     loop {
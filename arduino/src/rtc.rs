@@ -0,0 +1,72 @@
+//! DS3231 real-time clock driver
+//!
+//! Reads the time of day over I2C so the clock pattern keeps real time no matter how long each
+//! frame takes to render and write out.
+
+use embedded_hal::i2c::I2c;
+use led_star::clock::{Time, TimeSource};
+
+/// I2C address of the DS3231
+const ADDRESS: u8 = 0x68;
+
+/// Register holding the seconds, followed by the minutes and hours
+const SECONDS_REGISTER: u8 = 0x00;
+
+/// Hours register flag for 12-hour mode
+const TWELVE_HOUR: u8 = 0x40;
+
+/// Hours register flag for PM in 12-hour mode
+const PM: u8 = 0x20;
+
+/// Time source backed by a DS3231 real-time clock
+pub struct Ds3231<I: I2c> {
+    i2c: I,
+    time: Time,
+}
+
+impl<I: I2c> Ds3231<I> {
+    /// Create a new driver, reading the current time straight away
+    pub fn new(i2c: I) -> Self {
+        let mut rtc = Self {
+            i2c,
+            time: Time::default(),
+        };
+        rtc.tick();
+        rtc
+    }
+
+    /// Read the time from the chip, or `None` if the bus fails
+    fn read(&mut self) -> Option<Time> {
+        let mut registers = [0u8; 3];
+        self.i2c
+            .write_read(ADDRESS, &[SECONDS_REGISTER], &mut registers)
+            .ok()?;
+        let [seconds, minutes, hours] = registers;
+
+        let hours = if hours & TWELVE_HOUR != 0 {
+            let hour = bcd(hours & 0x1f) % 12;
+            if hours & PM != 0 { hour + 12 } else { hour }
+        } else {
+            bcd(hours & 0x3f)
+        };
+        Some(Time::new(hours, bcd(minutes & 0x7f), bcd(seconds & 0x7f)))
+    }
+}
+
+/// Decode a binary-coded decimal register
+fn bcd(value: u8) -> u8 {
+    (value >> 4) * 10 + (value & 0x0f)
+}
+
+impl<I: I2c> TimeSource for Ds3231<I> {
+    fn tick(&mut self) {
+        // Keep showing the last good time if the bus fails
+        if let Some(time) = self.read() {
+            self.time = time;
+        }
+    }
+
+    fn now(&self) -> Time {
+        self.time
+    }
+}
@@ -0,0 +1,392 @@
+use crate::{
+    color::Hsv,
+    graph::{Graph, Node},
+    osc,
+    pattern::{Index, Pattern, Surface},
+    rand,
+    star::Layout,
+    storage::Storage,
+};
+
+/// How each generation is computed from the previous one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rule {
+    /// Game of Life style rule over every neighbor in the star graph
+    ///
+    /// Bit `n` of `birth` brings a dead cell to life when it has `n` live neighbors, and bit `n`
+    /// of `survive` keeps a live cell alive. Cells have at most 4 neighbors.
+    Life { birth: u8, survive: u8 },
+    /// Elementary 1D rule, by Wolfram number, along each spine, tip and arc
+    ///
+    /// The ends of each strip see dead cells beyond them.
+    Elementary(u8),
+}
+
+impl Rule {
+    /// Births next to a single live cell, survives with one or two: grows and collapses in waves
+    pub const WAVES: Rule = Rule::Life {
+        birth: 0b0010,
+        survive: 0b0110,
+    };
+
+    /// Chaotic elementary rule 30
+    pub const CHAOS: Rule = Rule::Elementary(30);
+
+    /// Sierpinski-like elementary rule 90
+    pub const SIERPINSKI: Rule = Rule::Elementary(90);
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::CHAOS
+    }
+}
+
+/// A cellular automaton running on every LED of the star
+///
+/// Each LED is one cell, stored as a single bit. `bits` holds two generations, so it needs
+/// `2 * ceil(cells / 8)` bytes: 120 bytes for the ~480 logical LEDs of the default layout.
+///
+/// When the state dies out or stops changing, it is reseeded at random so it never settles.
+///
+/// - `rate` sets how many generations run each tick, from none (-128) up to ~4 (127)
+/// - `mutation` sets the chance each generation of flipping a random cell, from never (-128) to
+///   always (127)
+/// - `alive` and `dead` provide the colors of live and dead cells
+pub struct Automaton<Rate, Mutation, Alive, Dead, Bits>
+where
+    Rate: osc::Oscillator,
+    Mutation: osc::Oscillator,
+    Alive: Pattern,
+    Dead: Pattern,
+    Bits: Storage<Value = u8>,
+{
+    pub rule: Rule,
+    pub rate: Rate,
+    pub mutation: Mutation,
+    pub alive: Alive,
+    pub dead: Dead,
+    graph: Graph,
+    bits: Bits,
+    /// Bytes in each generation
+    half: u8,
+    /// Whether the current generation lives in the second half of `bits`
+    swapped: bool,
+    rate_frac: u8,
+}
+
+impl<Rate, Mutation, Alive, Dead, Bits> Automaton<Rate, Mutation, Alive, Dead, Bits>
+where
+    Rate: osc::Oscillator,
+    Mutation: osc::Oscillator,
+    Alive: Pattern,
+    Dead: Pattern,
+    Bits: Storage<Value = u8>,
+{
+    /// Creates an automaton with every cell dead, so the first generation seeds it at random
+    pub fn new(
+        layout: &impl Layout,
+        rule: Rule,
+        rate: Rate,
+        mutation: Mutation,
+        alive: Alive,
+        dead: Dead,
+        mut bits: Bits,
+    ) -> Self {
+        let graph = Graph::new(layout);
+        let half = graph.cells().div_ceil(8);
+        assert!(
+            bits.len() as u16 >= half * 2,
+            "Automaton needs {} bytes of state for {} cells, but only {} were provided",
+            half * 2,
+            graph.cells(),
+            bits.len()
+        );
+        for byte in bits.iter_mut() {
+            *byte = 0;
+        }
+
+        Self {
+            rule,
+            rate,
+            mutation,
+            alive,
+            dead,
+            graph,
+            bits,
+            half: half as u8,
+            swapped: false,
+            rate_frac: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn offset(&self, next: bool) -> u8 {
+        if self.swapped != next { self.half } else { 0 }
+    }
+
+    #[inline(always)]
+    fn get(&self, cell: u16) -> bool {
+        let byte = self.offset(false) + (cell / 8) as u8;
+        *self.bits.get(byte) & (1 << (cell % 8)) != 0
+    }
+
+    #[inline(always)]
+    fn put(&mut self, next: bool, cell: u16, alive: bool) {
+        let byte = self.offset(next) + (cell / 8) as u8;
+        let byte = self.bits.get_mut(byte);
+        let mask = 1 << (cell % 8);
+        if alive {
+            *byte |= mask;
+        } else {
+            *byte &= !mask;
+        }
+    }
+
+    /// Returns whether the cell at a node is alive
+    #[inline(always)]
+    pub fn is_alive(&self, node: Node) -> bool {
+        self.get(self.graph.cell(node))
+    }
+
+    /// Sets the cell at a node
+    #[inline(always)]
+    pub fn set(&mut self, node: Node, alive: bool) {
+        self.put(false, self.graph.cell(node), alive);
+    }
+
+    /// Kills every cell
+    pub fn clear(&mut self) {
+        for cell in 0..self.graph.cells() {
+            self.put(false, cell, false);
+        }
+    }
+
+    /// Brings roughly a third of the cells to life at random
+    pub fn seed(&mut self) {
+        for cell in 0..self.graph.cells() {
+            self.put(false, cell, rand::u8() < 85);
+        }
+    }
+
+    #[inline(always)]
+    fn next_state(&self, cell: u16) -> bool {
+        let node = self.graph.node(cell);
+        let alive = self.get(cell);
+        match self.rule {
+            Rule::Life { birth, survive } => {
+                let neighbors = self
+                    .graph
+                    .neighbors(node)
+                    .filter(|neighbor| self.get(self.graph.cell(*neighbor)))
+                    .count();
+                let mask = if alive { survive } else { birth };
+                mask & (1 << neighbors) != 0
+            }
+            Rule::Elementary(rule) => {
+                let len = self.graph.strip_len(node.surface);
+                let left = node.led > 0 && self.get(cell - 1);
+                let right = node.led + 1 < len && self.get(cell + 1);
+                let pattern = ((left as u8) << 2) | ((alive as u8) << 1) | right as u8;
+                rule & (1 << pattern) != 0
+            }
+        }
+    }
+
+    /// Computes the next generation
+    pub fn step(&mut self) {
+        let mut changed = false;
+        let mut population = 0u16;
+        for cell in 0..self.graph.cells() {
+            let alive = self.next_state(cell);
+            changed |= alive != self.get(cell);
+            population += alive as u16;
+            self.put(true, cell, alive);
+        }
+        self.swapped = !self.swapped;
+
+        if population == 0 || !changed {
+            self.seed();
+        }
+
+        if rand::u8() < osc::to_u8(self.mutation.get()) {
+            let cell = rand::below_u16(self.graph.cells());
+            let alive = self.get(cell);
+            self.put(false, cell, !alive);
+        }
+    }
+
+    #[inline(always)]
+    fn color(&self, node: Node, alive: Hsv, dead: Hsv) -> Hsv {
+        if self.is_alive(node) { alive } else { dead }
+    }
+}
+
+impl<Rate, Mutation, Alive, Dead, Bits> Pattern for Automaton<Rate, Mutation, Alive, Dead, Bits>
+where
+    Rate: osc::Oscillator,
+    Mutation: osc::Oscillator,
+    Alive: Pattern,
+    Dead: Pattern,
+    Bits: Storage<Value = u8>,
+{
+    fn tick(&mut self) {
+        self.rate.tick();
+        self.mutation.tick();
+        self.alive.tick();
+        self.dead.tick();
+
+        // Generations per tick in 2.6 fixed-point
+        let accumulated = self.rate_frac as u16 + osc::to_u8(self.rate.get()) as u16;
+        self.rate_frac = (accumulated & 0x3F) as u8;
+        for _ in 0..accumulated >> 6 {
+            self.step();
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let node = Node::new(Surface::Spine, spine.index, led.index);
+        self.color(
+            node,
+            self.alive.spine_color_at(spine, led),
+            self.dead.spine_color_at(spine, led),
+        )
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let node = Node::new(Surface::Tip, spine.index, led.index);
+        self.color(
+            node,
+            self.alive.spine_tip_color_at(spine, led),
+            self.dead.spine_tip_color_at(spine, led),
+        )
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let node = Node::new(Surface::Arc, arc.index, led.index);
+        self.color(
+            node,
+            self.alive.arc_color_at(arc, led),
+            self.dead.arc_color_at(arc, led),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{osc::Constant, star::FixedLayout};
+
+    const ALIVE: Hsv = Hsv::new(96, 255, 255);
+    const DEAD: Hsv = Hsv::new(0, 0, 0);
+
+    type Bits = [u8; 14];
+
+    fn automaton<Rate: osc::Oscillator>(
+        rule: Rule,
+        rate: Rate,
+    ) -> Automaton<Rate, Constant<{ i8::MIN }>, Hsv, Hsv, Bits> {
+        let layout = FixedLayout::new(4, 4, 9, 1, 3);
+        Automaton::new(
+            &layout,
+            rule,
+            rate,
+            Constant::<{ i8::MIN }>,
+            ALIVE,
+            DEAD,
+            [0u8; 14],
+        )
+    }
+
+    fn spine(automaton: &impl Pattern, spine: u8) -> String {
+        (0..9)
+            .map(|led| {
+                let color = automaton.spine_color_at(Index::new(spine, 4), Index::new(led, 9));
+                if color == ALIVE { '#' } else { '.' }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_elementary() {
+        let mut automaton = automaton(Rule::SIERPINSKI, Constant::<{ i8::MIN }>);
+        automaton.set(Node::new(Surface::Spine, 1, 4), true);
+
+        let mut rows = vec![];
+        for _ in 0..4 {
+            rows.push(spine(&automaton, 1));
+            automaton.step();
+        }
+        assert_eq!(rows, ["....#....", "...#.#...", "..#...#..", ".#.#.#.#."]);
+
+        // Other spines are left alone
+        assert_eq!(spine(&automaton, 0), ".........");
+    }
+
+    #[test]
+    fn test_life_crosses_arcs() {
+        let rule = Rule::Life {
+            birth: 0b0010,
+            survive: 0,
+        };
+        let mut automaton = automaton(rule, Constant::<{ i8::MIN }>);
+        automaton.set(Node::new(Surface::Spine, 0, 0), true);
+        automaton.step();
+
+        // Every neighbor of the center of spine 0 is born, across both arcs
+        assert!(!automaton.is_alive(Node::new(Surface::Spine, 0, 0)));
+        assert!(automaton.is_alive(Node::new(Surface::Spine, 0, 1)));
+        assert!(automaton.is_alive(Node::new(Surface::Arc, 0, 0)));
+        assert!(automaton.is_alive(Node::new(Surface::Arc, 3, 2)));
+        assert!(!automaton.is_alive(Node::new(Surface::Spine, 1, 0)));
+    }
+
+    #[test]
+    fn test_reseeds_when_stuck() {
+        rand::seed(11);
+        let mut automaton = automaton(Rule::WAVES, Constant::<{ i8::MIN }>);
+
+        // Starts empty and seeds itself on the first generation
+        automaton.step();
+        let alive = (0..9)
+            .filter(|led| automaton.is_alive(Node::new(Surface::Spine, 0, *led)))
+            .count();
+        assert!(alive > 0);
+
+        // Rule 0 kills everything, which triggers a reseed
+        automaton.rule = Rule::Elementary(0);
+        automaton.step();
+        let alive = (0..automaton.graph.cells())
+            .filter(|cell| automaton.get(*cell))
+            .count();
+        assert!(alive > 0);
+    }
+
+    #[test]
+    fn test_rate() {
+        let mut automaton = automaton(Rule::SIERPINSKI, Constant::<{ i8::MAX }>);
+        automaton.set(Node::new(Surface::Spine, 2, 4), true);
+
+        // ~4 generations per tick, starting with 3 while the remainder accumulates
+        automaton.tick();
+        assert_eq!(spine(&automaton, 2), ".#.#.#.#.");
+    }
+
+    #[test]
+    #[should_panic(expected = "Automaton needs 12 bytes of state for 44 cells")]
+    fn test_storage_too_small() {
+        let layout = FixedLayout::new(4, 4, 7, 1, 3);
+        Automaton::new(
+            &layout,
+            Rule::default(),
+            Constant::<0>,
+            Constant::<0>,
+            ALIVE,
+            DEAD,
+            [0u8; 11],
+        );
+    }
+}
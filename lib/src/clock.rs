@@ -0,0 +1,328 @@
+use crate::{
+    color::{Hsv, scale8},
+    pattern::{Index, Pattern},
+    star::Layout,
+};
+
+/// Brightness the seconds pulse loses per tick
+const PULSE_DECAY: u8 = 16;
+
+const SECONDS_PER_HOUR: u32 = 60 * 60;
+const SECONDS_PER_DAY: u32 = 24 * SECONDS_PER_HOUR;
+
+/// A time of day, to the second
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Time {
+    pub hours: u8,
+    pub minutes: u8,
+    pub seconds: u8,
+}
+
+impl Time {
+    pub const fn new(hours: u8, minutes: u8, seconds: u8) -> Self {
+        Self {
+            hours,
+            minutes,
+            seconds,
+        }
+    }
+
+    /// The time a number of seconds after midnight, wrapping around each day
+    pub const fn from_seconds(seconds: u32) -> Self {
+        let seconds = seconds % SECONDS_PER_DAY;
+        Self::new(
+            (seconds / SECONDS_PER_HOUR) as u8,
+            (seconds / 60 % 60) as u8,
+            (seconds % 60) as u8,
+        )
+    }
+
+    /// Seconds since midnight
+    pub const fn to_seconds(self) -> u32 {
+        self.hours as u32 * SECONDS_PER_HOUR + self.minutes as u32 * 60 + self.seconds as u32
+    }
+}
+
+/// Where a [`Clock`] reads the time of day from, such as a real-time clock chip
+pub trait TimeSource {
+    /// Called once per frame, so a source can poll its hardware or count frames
+    fn tick(&mut self) {}
+
+    /// The current time of day
+    fn now(&self) -> Time;
+}
+
+/// A fixed time, which never moves on
+impl TimeSource for Time {
+    #[inline(always)]
+    fn now(&self) -> Time {
+        *self
+    }
+}
+
+/// A software clock that keeps time by counting frames
+///
+/// This only keeps good time when frames are drawn at a steady, known rate, as in the visualizer
+/// and tests. Hardware should read a real-time clock instead.
+#[derive(Clone, Copy, Debug)]
+pub struct Counter {
+    seconds: u32,
+    ticks: u8,
+    ticks_per_second: u8,
+}
+
+impl Counter {
+    /// Starts the clock at `start`, moving on a second every `ticks_per_second` frames
+    pub fn new(start: Time, ticks_per_second: u8) -> Self {
+        assert!(
+            ticks_per_second > 0,
+            "Counter needs at least one tick per second"
+        );
+        Self {
+            seconds: start.to_seconds(),
+            ticks: 0,
+            ticks_per_second,
+        }
+    }
+}
+
+impl TimeSource for Counter {
+    fn tick(&mut self) {
+        self.ticks += 1;
+        if self.ticks >= self.ticks_per_second {
+            self.ticks = 0;
+            self.seconds = (self.seconds + 1) % SECONDS_PER_DAY;
+        }
+    }
+
+    #[inline(always)]
+    fn now(&self) -> Time {
+        Time::from_seconds(self.seconds)
+    }
+}
+
+/// A clock face, with spine 0 at twelve o'clock and the spines running clockwise
+///
+/// - The hour hand lights the whole of the hour spine along with its tip
+/// - The minute hand grows out along its spine, filling it over the minutes the spine covers
+/// - The seconds pulse in the arc covering the current second, flashing each time it changes
+///
+/// Each hand takes its color from its own pattern, and everything else is dark.
+pub struct Clock<Source, Hour, Minute, Second>
+where
+    Source: TimeSource,
+    Hour: Pattern,
+    Minute: Pattern,
+    Second: Pattern,
+{
+    pub source: Source,
+    pub hour: Hour,
+    pub minute: Minute,
+    pub second: Second,
+    time: Time,
+    pulse: u8,
+    spine_len: u8,
+}
+
+impl<Source, Hour, Minute, Second> Clock<Source, Hour, Minute, Second>
+where
+    Source: TimeSource,
+    Hour: Pattern,
+    Minute: Pattern,
+    Second: Pattern,
+{
+    pub fn new(
+        layout: &impl Layout,
+        source: Source,
+        hour: Hour,
+        minute: Minute,
+        second: Second,
+    ) -> Self {
+        Self {
+            time: source.now(),
+            source,
+            hour,
+            minute,
+            second,
+            pulse: 0,
+            spine_len: layout.spine_len_at(0),
+        }
+    }
+
+    #[inline(always)]
+    fn hour_spine(&self, spines: u8) -> u8 {
+        ((self.time.hours % 12) as u16 * spines as u16 / 12) as u8
+    }
+
+    /// The minute spine, and how many of its LEDs the minute hand fills
+    #[inline(always)]
+    fn minute_hand(&self, spines: u8) -> (u8, u8) {
+        let seconds = self.time.minutes as u32 * 60 + self.time.seconds as u32;
+        let position = seconds * spines as u32;
+        let spine = (position / SECONDS_PER_HOUR) as u8;
+        let fill = (position % SECONDS_PER_HOUR) * self.spine_len as u32 / SECONDS_PER_HOUR;
+        (spine, fill as u8 + 1)
+    }
+
+    #[inline(always)]
+    fn second_arc(&self, arcs: u8) -> u8 {
+        (self.time.seconds as u16 * arcs as u16 / 60) as u8
+    }
+}
+
+impl<Source, Hour, Minute, Second> Pattern for Clock<Source, Hour, Minute, Second>
+where
+    Source: TimeSource,
+    Hour: Pattern,
+    Minute: Pattern,
+    Second: Pattern,
+{
+    fn tick(&mut self) {
+        self.source.tick();
+        self.hour.tick();
+        self.minute.tick();
+        self.second.tick();
+
+        let now = self.source.now();
+        self.pulse = if now.seconds != self.time.seconds {
+            255
+        } else {
+            self.pulse.saturating_sub(PULSE_DECAY)
+        };
+        self.time = now;
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let (minute, fill) = self.minute_hand(spine.total);
+        if spine.index == minute && led.index < fill {
+            self.minute.spine_color_at(spine, led)
+        } else if spine.index == self.hour_spine(spine.total) {
+            self.hour.spine_color_at(spine, led)
+        } else {
+            Hsv::new(0, 0, 0)
+        }
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        if spine.index == self.hour_spine(spine.total) {
+            self.hour.spine_tip_color_at(spine, led)
+        } else {
+            Hsv::new(0, 0, 0)
+        }
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        if arc.index == self.second_arc(arc.total) {
+            let mut color = self.second.arc_color_at(arc, led);
+            color.v = scale8(color.v, self.pulse);
+            color
+        } else {
+            Hsv::new(0, 0, 0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::star::FixedLayout;
+
+    const HOUR: Hsv = Hsv::new(10, 255, 255);
+    const MINUTE: Hsv = Hsv::new(20, 255, 255);
+    const SECOND: Hsv = Hsv::new(30, 255, 255);
+    const TIP: Index = Index::new(0, 1);
+
+    fn clock<S: TimeSource>(source: S) -> Clock<S, Hsv, Hsv, Hsv> {
+        Clock::new(
+            &FixedLayout::new(12, 12, 10, 1, 3),
+            source,
+            HOUR,
+            MINUTE,
+            SECOND,
+        )
+    }
+
+    /// Hue of every LED along a spine, with 0 for dark LEDs
+    fn spine_hues(pattern: &impl Pattern, spine: u8) -> Vec<u8> {
+        (0..10)
+            .map(|led| pattern.spine_color_at(Index::new(spine, 12), Index::new(led, 10)))
+            .map(|color| if color.v > 0 { color.h } else { 0 })
+            .collect()
+    }
+
+    #[test]
+    fn test_time() {
+        let time = Time::new(13, 45, 30);
+        assert_eq!(Time::from_seconds(time.to_seconds()), time);
+        assert_eq!(Time::from_seconds(SECONDS_PER_DAY + 61), Time::new(0, 1, 1));
+
+        let mut counter = Counter::new(Time::new(23, 59, 59), 2);
+        counter.tick();
+        assert_eq!(counter.now(), Time::new(23, 59, 59));
+        counter.tick();
+        assert_eq!(counter.now(), Time::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_hands() {
+        // Twenty-five to four in the afternoon
+        let mut clock = clock(Time::new(15, 35, 0));
+        clock.tick();
+
+        assert_eq!(spine_hues(&clock, 3), [10; 10]);
+        assert_eq!(clock.spine_tip_color_at(Index::new(3, 12), TIP), HOUR);
+
+        // The minute hand has only just reached the seven o'clock spine
+        assert_eq!(spine_hues(&clock, 7), [20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(clock.spine_tip_color_at(Index::new(7, 12), TIP).v, 0);
+
+        // It grows out along the spine as the minutes pass
+        clock.source = Time::new(15, 38, 0);
+        clock.tick();
+        assert_eq!(spine_hues(&clock, 7), [20, 20, 20, 20, 20, 20, 20, 0, 0, 0]);
+
+        for spine in [0, 1, 2, 4, 5, 6, 8, 9, 10, 11] {
+            assert_eq!(spine_hues(&clock, spine), [0; 10]);
+        }
+    }
+
+    #[test]
+    fn test_minute_over_hour() {
+        let mut clock = clock(Time::new(6, 32, 30));
+        clock.tick();
+        assert_eq!(
+            spine_hues(&clock, 6),
+            [20, 20, 20, 20, 20, 20, 10, 10, 10, 10]
+        );
+    }
+
+    #[test]
+    fn test_seconds_pulse() {
+        let arc = |clock: &Clock<Counter, Hsv, Hsv, Hsv>, arc| {
+            clock.arc_color_at(Index::new(arc, 12), Index::new(1, 3))
+        };
+
+        let mut clock = clock(Counter::new(Time::new(0, 0, 14), 20));
+        for _ in 0..19 {
+            clock.tick();
+        }
+        assert_eq!(arc(&clock, 2).v, 0);
+
+        // The pulse flashes in the arc covering 15-19 seconds as the second changes, then fades
+        clock.tick();
+        assert_eq!(arc(&clock, 3).h, SECOND.h);
+        assert!(arc(&clock, 3).v > 250);
+        clock.tick();
+        assert!(arc(&clock, 3).v < 255);
+        for _ in 0..18 {
+            clock.tick();
+        }
+        assert_eq!(arc(&clock, 3).v, 0);
+        for a in [0, 1, 2, 4, 5, 6, 7, 8, 9, 10, 11] {
+            assert_eq!(arc(&clock, a).v, 0);
+        }
+    }
+}
@@ -20,6 +20,32 @@ impl Hsv {
         Self { h, s, v }
     }
 
+    /// Linearly interpolate towards another color by a factor (0-255)
+    ///
+    /// Hue takes the shortest path around the color wheel.
+    #[inline]
+    pub fn lerp(self, other: Hsv, t: u8) -> Hsv {
+        let hue_delta = other.h.wrapping_sub(self.h) as i8 as i16;
+        let h = self.h.wrapping_add((hue_delta * t as i16 / 255) as u8);
+        Hsv {
+            h,
+            s: lerp8(self.s, other.s, t),
+            v: lerp8(self.v, other.v, t),
+        }
+    }
+
+    /// Dim the color towards black by a fraction (0-255)
+    #[inline(always)]
+    pub fn fade_to_black_by(self, amount: u8) -> Hsv {
+        if amount == 0 {
+            return self;
+        }
+        Hsv {
+            v: scale8(self.v, 255 - amount),
+            ..self
+        }
+    }
+
     /// Convert HSV to RGB
     ///
     /// This uses a fast approximation suitable for embedded systems,
@@ -83,11 +109,22 @@ impl Rgb {
 /// Scale a value by a factor (0-255)
 /// Returns (value * scale + 1) / 256, which provides better rounding
 #[inline(always)]
-fn scale8(value: u8, scale: u8) -> u8 {
+pub fn scale8(value: u8, scale: u8) -> u8 {
     let product = value as u16 * scale as u16;
     ((product + 1) >> 8) as u8
 }
 
+/// Linearly interpolate between two values by a factor (0-255)
+/// Returns `a` at 0 and `b` at 255
+#[inline(always)]
+pub fn lerp8(a: u8, b: u8, t: u8) -> u8 {
+    if b >= a {
+        a + ((b - a) as u16 * t as u16 / 255) as u8
+    } else {
+        a - ((a - b) as u16 * t as u16 / 255) as u8
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,6 +192,36 @@ mod tests {
         assert_eq!(rgb.b, 0);
     }
 
+    #[test]
+    fn test_hsv_lerp() {
+        let a = Hsv::new(250, 0, 0);
+        let b = Hsv::new(10, 200, 255);
+
+        assert_eq!(a.lerp(b, 0), a);
+        assert_eq!(a.lerp(b, 255), b);
+
+        // Hue takes the short way through 0
+        let mid = a.lerp(b, 128);
+        assert_eq!(mid, Hsv::new(2, 100, 128));
+    }
+
+    #[test]
+    fn test_fade_to_black_by() {
+        let color = Hsv::new(10, 20, 200);
+        assert_eq!(color.fade_to_black_by(0), color);
+        assert_eq!(color.fade_to_black_by(128), Hsv::new(10, 20, 99));
+        assert_eq!(color.fade_to_black_by(255).v, 0);
+    }
+
+    #[test]
+    fn test_lerp8() {
+        assert_eq!(lerp8(0, 255, 0), 0);
+        assert_eq!(lerp8(0, 255, 255), 255);
+        assert_eq!(lerp8(0, 200, 128), 100);
+        assert_eq!(lerp8(200, 0, 128), 100);
+        assert_eq!(lerp8(10, 10, 77), 10);
+    }
+
     #[test]
     fn test_scale8() {
         // Test the scale8 function - uses fast approximation (>> 8 instead of / 255)
@@ -1,9 +1,24 @@
 use crate::{
+    automaton::{Automaton, Rule},
+    clock::{Clock, TimeSource},
     color::Hsv,
+    fire::Fire,
+    gradient::{Axis, Gradient},
+    graph::{Graph, Path, Segment},
+    layer::{Blend, Layer},
+    lightning::Lightning,
+    mask::{Mask, Region},
     osc::*,
-    pattern::{Compound, Oscillator, Pattern, PerSpine},
-    streak::StreakSpawner,
+    param::{Knob, Params, Spec},
+    pattern::{Compound, Oscillator, Pattern, PerSpine, Seek},
+    ripple::{Ring, Ripple},
+    star::FixedLayout,
+    streak::{PathStreak, StreakSpawner},
+    tip::{Arrivals, Flash, Glow, Sparkle},
+    transform::Rotate,
+    twinkle::Twinkle,
 };
+use core::ops::Deref;
 
 const SPINE_LEN: u8 = 70 / 2;
 const TIP_LEN: u8 = 0;
@@ -19,6 +34,13 @@ const LED_COUNT: u16 = {
     spines + arcs + tip_len
 };
 
+/// Logical LEDs, with the outgoing and returning halves of each spine counted once
+const CELL_COUNT: u16 = {
+    let spines = SPINE_COUNT as u16 * (SPINE_LEN as u16 + TIP_LEN as u16);
+    let arcs = ARC_COUNT as u16 * ARC_LEN as u16;
+    spines + arcs
+};
+
 pub struct Layout;
 
 impl crate::star::Layout for Layout {
@@ -57,15 +79,44 @@ pub fn layout() -> impl crate::star::Layout {
     Layout
 }
 
+/// The default layout as a [`FixedLayout`], so it can be swapped for another at runtime
+pub fn fixed_layout() -> FixedLayout {
+    FixedLayout::new(SPINE_COUNT, ARC_COUNT, SPINE_LEN, TIP_LEN, ARC_LEN)
+}
+
+/// The default layout with a single LED at the end of each spine, for patterns that light tips
+pub fn tip_layout() -> FixedLayout {
+    FixedLayout::new(SPINE_COUNT, ARC_COUNT, SPINE_LEN, 1, ARC_LEN)
+}
+
 pub fn pattern() -> impl Pattern {
     Compound {
         spine: spines::<{ SPINE_COUNT as usize }>(),
-        // TODO
+        // TIP_LEN is 0, so there are no tips to light; `tips_pattern` shows them on `tip_layout`
         tip: Hsv::new(0, 0, 0),
         arc: arc_pattern(),
     }
 }
 
+/// The default spines with glowing tips that flash as streaks arrive and sparkle at random
+///
+/// Built for [`tip_layout`], since the default layout has no tips.
+pub fn tips_pattern() -> impl Pattern {
+    const SPINES: usize = SPINE_COUNT as usize;
+    let layout = tip_layout();
+
+    let glow = Glow::new(&layout, Constant::<-96>, spines::<SPINES>(), [0u8; SPINES]);
+    let flash = Flash::new(&layout, Constant::<-64>, glow, [0u8; SPINES], [0u8; SPINES]);
+    let sparkle = Sparkle::new(
+        &layout,
+        Constant::<-124>, // ~1 sparkle every 50 ticks
+        Constant::<-96>,  // fade out over ~50 ticks
+        flash,
+        [0u8; SPINES],
+    );
+    Mask::new(Region::Arcs, arc_pattern(), sparkle)
+}
+
 pub fn arc_pattern() -> impl Pattern {
     let osc = Oscillator {
         h: sawtooth(),
@@ -80,33 +131,211 @@ pub fn arc_pattern() -> impl Pattern {
     )
 }
 
-pub fn spine_pattern(spine: u8) -> impl Pattern {
-    let values_per_spine = 255 / SPINE_COUNT;
-    let phase = (spine * values_per_spine) as i8;
+/// Random white streaks shooting out along a spine
+pub fn spine_pattern() -> impl Pattern + Arrivals {
+    let white = Oscillator {
+        h: Constant::<0>,
+        s: Constant::<{ i8::MIN }>,
+        v: Constant::<127>,
+    };
 
-    let osc = Oscillator {
+    StreakSpawner::new(
+        random_pulse(Constant::<5>, Constant::<{ i8::MIN }>), // randomly spawn streaks
+        rng_normal(Constant::<0>, Constant::<64>),            // lengths cluster around ~16 LEDs
+        rng_normal(Constant::<0>, Constant::<96>),            // velocities cluster around ~1x speed
+        Constant::<{ SPINE_LEN as i8 }>,                      // Total LEDs in spine
+        white,
+        [crate::streak::StreakState::default(); 8],
+    )
+}
+
+pub fn spines<const LEN: usize>() -> impl Pattern + Arrivals {
+    // A single set of oscillators colors every spine, with the hue phase spread around the star
+    let color = Oscillator {
         // rotate the hue around the color wheel
-        h: sawtooth().add(phase),
+        h: sawtooth().spread(1),
         // oscillate the saturation
         s: triangle(),
         // max value by default
         v: 127i8,
     };
+    let storage: [_; LEN] = core::array::from_fn(|_| spine_pattern());
+    let streaks = Layer::new(
+        color,
+        PerSpine::new(storage),
+        Blend::Multiply,
+        Constant::<127>,
+    );
+    // Orient spine 0 of the pattern to the physical layout
+    Rotate::new(Constant::<{ SPINE_COUNT as i8 / 2 - 1 }>, streaks)
+}
 
-    StreakSpawner::new(
-        random_pulse(Constant::<5>, Constant::<{ i8::MIN }>), // randomly spawn streaks
-        rng().max(Constant::<2>),                             // randomize lengths
-        rng(),                                                // randomize velocities
-        Constant::<{ SPINE_LEN as i8 }>,                      // Total LEDs in spine
+pub static TWINKLE_PARAMS: [Spec; 2] = [
+    Spec::full("density", -96), // ~1 new twinkle every other tick
+    Spec::full("fade", -112),   // fade out over ~60 ticks
+];
+
+/// Slowly drifting sparkles over the whole star
+pub fn twinkle_pattern<P>(params: P) -> impl Pattern
+where
+    P: Deref<Target = Params<2>> + Clone,
+{
+    let osc = Oscillator {
+        h: sawtooth().freq(Constant::<-128>),
+        s: Constant::<0>,
+        v: Constant::<127>,
+    };
+
+    Twinkle::new(
+        &Layout,
+        Knob::new(params.clone(), "density"),
+        Knob::new(params, "fade"),
         osc,
-        [crate::streak::StreakState::default(); 8],
+        [0u8; CELL_COUNT.div_ceil(2) as usize],
     )
 }
 
-pub fn spines<const LEN: usize>() -> impl Pattern {
-    let storage: [_; LEN] = core::array::from_fn(|v| {
-        let v = (v + SPINE_COUNT as usize / 2 - 1) % SPINE_COUNT as usize;
-        spine_pattern(v as _)
+pub static FIRE_PARAMS: [Spec; 2] = [Spec::full("cooling", -40), Spec::full("sparking", 40)];
+
+/// A flame rising along every spine
+pub fn fire_pattern<P>(params: P) -> impl Pattern
+where
+    P: Deref<Target = Params<2>> + Clone,
+{
+    let storage: [_; SPINE_COUNT as usize] = core::array::from_fn(|_| {
+        Fire::new(
+            Knob::new(params.clone(), "cooling"),
+            Knob::new(params.clone(), "sparking"),
+            [0u8; SPINE_LEN as usize],
+        )
     });
     PerSpine::new(storage)
 }
+
+pub static RIPPLE_PARAMS: [Spec; 3] = [
+    Spec::full("speed", -64),
+    Spec::full("width", -96),
+    Spec::full("decay", -112),
+];
+
+/// Rings pulsing out from the center and on into the arcs
+pub fn ripple_pattern<P>(params: P) -> impl Pattern
+where
+    P: Deref<Target = Params<3>> + Clone,
+{
+    let osc = Oscillator {
+        h: sawtooth().freq(Constant::<-64>),
+        s: Constant::<127>,
+        v: Constant::<127>,
+    };
+
+    Ripple::new(
+        &Layout,
+        sawtooth().freq(Constant::<64>), // a new ring every ~100 ticks
+        Knob::new(params.clone(), "speed"),
+        Knob::new(params.clone(), "width"),
+        Knob::new(params, "decay"),
+        true,
+        osc,
+        [Ring::default(); 8],
+    )
+}
+
+/// A rainbow turning around the star
+pub fn rainbow_pattern() -> impl Seek {
+    Gradient::hue(
+        Axis::Around,
+        sawtooth(),
+        sawtooth().add(-1i8),
+        Hsv::new(0, 255, 255),
+    )
+}
+
+pub static AUTOMATON_PARAMS: [Spec; 2] = [
+    Spec::full("rate", -112),    // a generation every ~4 ticks
+    Spec::full("mutation", -64), // occasional mutations
+];
+
+/// A chaotic cellular automaton colored by a rainbow around the star
+pub fn automaton_pattern<P>(params: P) -> impl Pattern
+where
+    P: Deref<Target = Params<2>> + Clone,
+{
+    let alive = Gradient::hue(
+        Axis::Around,
+        sawtooth().freq(Constant::<-128>),
+        sawtooth().freq(Constant::<-128>).add(-1i8),
+        Hsv::new(0, 255, 255),
+    );
+
+    Automaton::new(
+        &Layout,
+        Rule::CHAOS,
+        Knob::new(params.clone(), "rate"),
+        Knob::new(params, "mutation"),
+        alive,
+        Hsv::new(0, 0, 0),
+        [0u8; (CELL_COUNT.div_ceil(8) * 2) as usize],
+    )
+}
+
+pub static ORBIT_PARAMS: [Spec; 2] = [
+    Spec::full("length", 64),    // ~23 LEDs
+    Spec::full("velocity", 127), // 2 LEDs per tick
+];
+
+/// A single streak travelling out and back along each spine in turn, crossing over the arcs
+pub fn orbit_pattern<P>(params: P) -> impl Pattern
+where
+    P: Deref<Target = Params<2>> + Clone,
+{
+    let segments: [_; SPINE_COUNT as usize * 3] = core::array::from_fn(|index| {
+        let spine = (index / 3) as u8;
+        match index % 3 {
+            0 => Segment::spine_out(spine),
+            1 => Segment::spine_in(spine),
+            _ => Segment::arc(spine),
+        }
+    });
+    let osc = Oscillator {
+        h: sawtooth(),
+        s: Constant::<127>,
+        v: Constant::<127>,
+    };
+
+    PathStreak::new(
+        Knob::new(params.clone(), "length"),
+        Knob::new(params, "velocity"),
+        osc,
+        Path::new(Graph::new(&Layout), segments),
+    )
+}
+
+pub static LIGHTNING_PARAMS: [Spec; 1] = [
+    Spec::full("decay", -64), // each flash lasts ~10 ticks
+];
+
+/// Bluish-white bolts of lightning striking out along random spines
+pub fn lightning_pattern<P>(params: P) -> impl Pattern
+where
+    P: Deref<Target = Params<1>> + Clone,
+{
+    Lightning::new(
+        &Layout,
+        random_pulse(Constant::<40>, Constant::<120>), // a bolt every 40-120 ticks
+        Knob::new(params, "decay"),
+        Hsv::new(160, 64, 255),
+    )
+}
+
+/// A clock face showing the time from `source`, with an orange hour hand, a cyan minute hand and
+/// white seconds
+pub fn clock_pattern<S: TimeSource>(source: S) -> impl Pattern {
+    Clock::new(
+        &Layout,
+        source,
+        Hsv::new(24, 255, 255),
+        Hsv::new(128, 255, 255),
+        Hsv::new(0, 0, 255),
+    )
+}
@@ -0,0 +1,198 @@
+use crate::{
+    color::Hsv,
+    osc,
+    pattern::{Index, Pattern},
+    rand,
+    storage::Storage,
+};
+
+/// Hue the flame reaches at its hottest, before washing out to white
+const YELLOW: u8 = 43;
+
+/// Maps a heat value onto a black body palette
+///
+/// Heat runs from black through red and yellow up to white.
+#[inline]
+pub fn heat_color(heat: u8) -> Hsv {
+    // Split the heat into three sections of 64 steps each
+    let t = (heat as u16 * 191 / 255) as u8;
+    let ramp = (t & 0x3F) << 2;
+    match t >> 6 {
+        0 => Hsv::new(0, 255, ramp),
+        1 => Hsv::new((ramp as u16 * YELLOW as u16 / 255) as u8, 255, 255),
+        _ => Hsv::new(YELLOW, 255 - ramp, 255),
+    }
+}
+
+/// A flame column rising from the center of a spine
+///
+/// This follows the classic Fire2012 simulation: every tick each cell cools a little, heat drifts
+/// up and diffuses, and new sparks ignite near the center. Use one per spine with
+/// [`PerSpine`](crate::pattern::PerSpine). `heat` holds one byte per cell, and the cells are
+/// stretched to fit the spine if it has a different number of LEDs.
+///
+/// - `cooling` sets how quickly the flame cools, which makes it shorter
+/// - `sparking` sets the chance of a new spark igniting each tick
+///
+/// Arcs are left dark, and the tip glows with the heat of the last cell.
+pub struct Fire<Cooling, Sparking, Heat>
+where
+    Cooling: osc::Oscillator,
+    Sparking: osc::Oscillator,
+    Heat: Storage<Value = u8>,
+{
+    pub cooling: Cooling,
+    pub sparking: Sparking,
+    heat: Heat,
+}
+
+impl<Cooling, Sparking, Heat> Fire<Cooling, Sparking, Heat>
+where
+    Cooling: osc::Oscillator,
+    Sparking: osc::Oscillator,
+    Heat: Storage<Value = u8>,
+{
+    pub fn new(cooling: Cooling, sparking: Sparking, heat: Heat) -> Self {
+        assert!(!heat.is_empty(), "Fire needs at least one heat cell");
+        Self {
+            cooling,
+            sparking,
+            heat,
+        }
+    }
+
+    /// Returns the heat of a cell
+    #[inline(always)]
+    pub fn heat_at(&self, cell: u8) -> u8 {
+        *self.heat.get(cell)
+    }
+
+    fn cool(&mut self) {
+        let len = self.heat.len() as u16;
+        let max = osc::to_u8(self.cooling.get()) as u16 * 10 / len + 2;
+        let max = max.min(u8::MAX as u16);
+        for cell in self.heat.iter_mut() {
+            let amount = rand::below_u16(max + 1) as u8;
+            *cell = cell.saturating_sub(amount);
+        }
+    }
+
+    fn diffuse(&mut self) {
+        for cell in (2..self.heat.len()).rev() {
+            let below = *self.heat.get(cell - 1) as u16;
+            let further = *self.heat.get(cell - 2) as u16;
+            *self.heat.get_mut(cell) = ((below + further * 2) / 3) as u8;
+        }
+    }
+
+    fn spark(&mut self) {
+        if rand::u8() >= osc::to_u8(self.sparking.get()) {
+            return;
+        }
+        // Sparks ignite in the bottom eighth of the column
+        let zone = self.heat.len() / 8 + 1;
+        let cell = rand::below_u16(zone as u16) as u8;
+        let heat = self.heat.get_mut(cell);
+        *heat = heat.saturating_add(rand::range_u8(160, 255));
+    }
+}
+
+impl<Cooling, Sparking, Heat> Pattern for Fire<Cooling, Sparking, Heat>
+where
+    Cooling: osc::Oscillator,
+    Sparking: osc::Oscillator,
+    Heat: Storage<Value = u8>,
+{
+    fn tick(&mut self) {
+        self.cooling.tick();
+        self.sparking.tick();
+
+        self.cool();
+        self.diffuse();
+        self.spark();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, _spine: Index, led: Index) -> Hsv {
+        let cell = led.index as u16 * self.heat.len() as u16 / led.total as u16;
+        heat_color(self.heat_at(cell as u8))
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, _spine: Index, _led: Index) -> Hsv {
+        heat_color(self.heat_at(self.heat.len() - 1))
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, _arc: Index, _led: Index) -> Hsv {
+        Hsv::new(0, 0, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{osc::Constant, pattern::PerSpine};
+
+    #[test]
+    fn test_heat_color() {
+        assert_eq!(heat_color(0), Hsv::new(0, 255, 0));
+        assert_eq!(heat_color(255), Hsv::new(YELLOW, 3, 255));
+
+        // Brightness rises before the hue shifts towards yellow
+        let mut previous = heat_color(0);
+        for heat in 1..=255 {
+            let color = heat_color(heat);
+            assert!(color.v >= previous.v);
+            assert!(color.h >= previous.h);
+            previous = color;
+        }
+    }
+
+    #[test]
+    fn test_flame_rises_from_center() {
+        rand::seed(7);
+        let mut fire = Fire::new(Constant::<-80>, Constant::<80>, [0u8; 35]);
+
+        let mut bottom = 0u32;
+        let mut top = 0u32;
+        for _ in 0..200 {
+            fire.tick();
+            bottom += (0..8).map(|cell| fire.heat_at(cell) as u32).sum::<u32>();
+            top += (27..35).map(|cell| fire.heat_at(cell) as u32).sum::<u32>();
+        }
+
+        assert!(bottom > 0);
+        assert!(bottom > top * 2, "bottom {bottom} top {top}");
+    }
+
+    #[test]
+    fn test_no_sparks() {
+        let mut fire = Fire::new(Constant::<0>, Constant::<{ i8::MIN }>, [0u8; 35]);
+        for _ in 0..100 {
+            fire.tick();
+        }
+        let led = Index::new(0, 35);
+        assert_eq!(fire.spine_color_at(Index::new(0, 1), led).v, 0);
+    }
+
+    #[test]
+    fn test_per_spine() {
+        rand::seed(3);
+        let fire = || Fire::new(Constant::<-64>, Constant::<127>, [0u8; 8]);
+        let mut pattern = PerSpine::new([fire(), fire()]);
+        for _ in 0..20 {
+            pattern.tick();
+        }
+
+        // Every spine burns, and the cells stretch to cover longer spines
+        for spine in 0..2 {
+            let spine = Index::new(spine, 2);
+            let lit = (0..16)
+                .filter(|led| pattern.spine_color_at(spine, Index::new(*led, 16)).v > 0)
+                .count();
+            assert!(lit > 0);
+            assert_eq!(pattern.arc_color_at(spine, Index::new(0, 5)).v, 0);
+        }
+    }
+}
@@ -0,0 +1,494 @@
+use crate::{
+    color::Hsv,
+    layer::Blend,
+    osc,
+    pattern::{Index, Pattern},
+    star::Layout,
+    storage::Storage,
+};
+
+/// Draws into a [`FrameBuffer`] on top of whatever is left from the previous frame
+pub trait Painter {
+    fn tick(&mut self);
+    fn paint<Rows>(&mut self, canvas: &mut Canvas<'_, Rows>)
+    where
+        Rows: Storage,
+        Rows::Value: Storage<Value = Hsv>;
+}
+
+/// Paints a regular pattern into the frame buffer, keeping whichever color is brighter
+///
+/// Wrapping a pattern in a stamp gives it a fading afterglow.
+pub struct Stamp<Inner>
+where
+    Inner: Pattern,
+{
+    pub inner: Inner,
+}
+
+impl<Inner> Stamp<Inner>
+where
+    Inner: Pattern,
+{
+    pub fn new(inner: Inner) -> Self {
+        Self { inner }
+    }
+}
+
+impl<Inner> Painter for Stamp<Inner>
+where
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.inner.tick();
+    }
+
+    fn paint<Rows>(&mut self, canvas: &mut Canvas<'_, Rows>)
+    where
+        Rows: Storage,
+        Rows::Value: Storage<Value = Hsv>,
+    {
+        let spines = canvas.spines();
+        let arcs = canvas.arcs();
+        for spine in 0..spines {
+            let spine_index = Index::new(spine, spines);
+            for led in 0..canvas.spine_len() {
+                let led_index = Index::new(led, canvas.spine_len());
+                let color = self.inner.spine_color_at(spine_index, led_index);
+                canvas.paint_spine(spine, led, color);
+            }
+            for led in 0..canvas.tip_len() {
+                let led_index = Index::new(led, canvas.tip_len());
+                let color = self.inner.spine_tip_color_at(spine_index, led_index);
+                canvas.paint_tip(spine, led, color);
+            }
+        }
+        for arc in 0..arcs {
+            let arc_index = Index::new(arc, arcs);
+            for led in 0..canvas.arc_len() {
+                let led_index = Index::new(led, canvas.arc_len());
+                let color = self.inner.arc_color_at(arc_index, led_index);
+                canvas.paint_arc(arc, led, color);
+            }
+        }
+    }
+}
+
+/// Shape of the star as stored in the frame buffer rows
+#[derive(Clone, Copy, Debug)]
+struct Shape {
+    spines: u8,
+    arcs: u8,
+    spine_len: u8,
+    arc_len: u8,
+    tip_len: u8,
+}
+
+impl Shape {
+    #[inline(always)]
+    fn arc_row(&self, arc: u8) -> u8 {
+        self.spines + arc
+    }
+
+    #[inline(always)]
+    fn tip_row(&self, spine: u8) -> u8 {
+        self.spines + self.arcs + spine
+    }
+
+    /// Number of rows needed to cover every strip
+    #[inline(always)]
+    fn rows(&self) -> u8 {
+        let tips = if self.tip_len > 0 { self.spines } else { 0 };
+        self.spines + self.arcs + tips
+    }
+
+    /// Length of the strip stored in a row
+    #[inline(always)]
+    fn row_len(&self, row: u8) -> u8 {
+        if row < self.spines {
+            self.spine_len
+        } else if row < self.spines + self.arcs {
+            self.arc_len
+        } else {
+            self.tip_len
+        }
+    }
+}
+
+/// Mutable view of the frame buffer handed to a [`Painter`]
+///
+/// Spines are indexed from the center out, arcs and tips from their first LED.
+pub struct Canvas<'a, Rows> {
+    rows: &'a mut Rows,
+    shape: Shape,
+}
+
+impl<Rows> Canvas<'_, Rows>
+where
+    Rows: Storage,
+    Rows::Value: Storage<Value = Hsv>,
+{
+    #[inline(always)]
+    pub fn spines(&self) -> u8 {
+        self.shape.spines
+    }
+
+    #[inline(always)]
+    pub fn arcs(&self) -> u8 {
+        self.shape.arcs
+    }
+
+    #[inline(always)]
+    pub fn spine_len(&self) -> u8 {
+        self.shape.spine_len
+    }
+
+    #[inline(always)]
+    pub fn arc_len(&self) -> u8 {
+        self.shape.arc_len
+    }
+
+    #[inline(always)]
+    pub fn tip_len(&self) -> u8 {
+        self.shape.tip_len
+    }
+
+    #[inline(always)]
+    pub fn spine(&mut self, spine: u8, led: u8) -> &mut Hsv {
+        self.rows.get_mut(spine).get_mut(led)
+    }
+
+    #[inline(always)]
+    pub fn arc(&mut self, arc: u8, led: u8) -> &mut Hsv {
+        let row = self.shape.arc_row(arc);
+        self.rows.get_mut(row).get_mut(led)
+    }
+
+    #[inline(always)]
+    pub fn tip(&mut self, spine: u8, led: u8) -> &mut Hsv {
+        let row = self.shape.tip_row(spine);
+        self.rows.get_mut(row).get_mut(led)
+    }
+
+    /// Paints a spine LED, keeping the existing color if it is brighter
+    #[inline(always)]
+    pub fn paint_spine(&mut self, spine: u8, led: u8, color: Hsv) {
+        let pixel = self.spine(spine, led);
+        *pixel = Blend::Lighten.apply(*pixel, color);
+    }
+
+    /// Paints an arc LED, keeping the existing color if it is brighter
+    #[inline(always)]
+    pub fn paint_arc(&mut self, arc: u8, led: u8, color: Hsv) {
+        let pixel = self.arc(arc, led);
+        *pixel = Blend::Lighten.apply(*pixel, color);
+    }
+
+    /// Paints a tip LED, keeping the existing color if it is brighter
+    #[inline(always)]
+    pub fn paint_tip(&mut self, spine: u8, led: u8, color: Hsv) {
+        let pixel = self.tip(spine, led);
+        *pixel = Blend::Lighten.apply(*pixel, color);
+    }
+}
+
+/// Keeps the previous frame around and lets it decay
+///
+/// Every tick the stored frame fades towards black and blurs along each strip, then the inner
+/// [`Painter`] draws the new frame on top. Trails and afterglow come for free, at the cost of
+/// keeping a color per LED: `rows` holds one row per spine, then one per arc, then one per tip
+/// (if the layout has tips), and each row needs room for its strip.
+///
+/// - `fade` sets how much of the previous frame is lost each tick, from none (-128) to all (127)
+/// - `blur` sets how much of each LED spreads to its neighbors, from none (-128) to all (127)
+pub struct FrameBuffer<Fade, Blur, Inner, Rows>
+where
+    Fade: osc::Oscillator,
+    Blur: osc::Oscillator,
+    Inner: Painter,
+    Rows: Storage,
+    Rows::Value: Storage<Value = Hsv>,
+{
+    pub fade: Fade,
+    pub blur: Blur,
+    pub inner: Inner,
+    rows: Rows,
+    shape: Shape,
+}
+
+impl<Fade, Blur, Inner, Rows> FrameBuffer<Fade, Blur, Inner, Rows>
+where
+    Fade: osc::Oscillator,
+    Blur: osc::Oscillator,
+    Inner: Painter,
+    Rows: Storage,
+    Rows::Value: Storage<Value = Hsv>,
+{
+    pub fn new(layout: &impl Layout, fade: Fade, blur: Blur, inner: Inner, rows: Rows) -> Self {
+        let shape = Shape {
+            spines: layout.spines(),
+            arcs: layout.arcs(),
+            spine_len: layout.spine_len_at(0),
+            arc_len: layout.arc_len_at(0),
+            tip_len: layout.tip_len_at(0),
+        };
+        assert!(
+            rows.len() >= shape.rows(),
+            "FrameBuffer needs {} rows, but only {} were provided",
+            shape.rows(),
+            rows.len()
+        );
+        for (index, row) in rows.iter().enumerate().take(shape.rows() as usize) {
+            let needed = shape.row_len(index as u8);
+            assert!(
+                row.len() >= needed,
+                "FrameBuffer row {index} needs {needed} LEDs, but only {} were provided",
+                row.len()
+            );
+        }
+
+        Self {
+            fade,
+            blur,
+            inner,
+            rows,
+            shape,
+        }
+    }
+
+    /// Clears the stored frame to black
+    pub fn clear(&mut self) {
+        for row in self.rows.iter_mut() {
+            for pixel in row.iter_mut() {
+                *pixel = Hsv::new(0, 0, 0);
+            }
+        }
+    }
+
+    fn decay(&mut self) {
+        let fade = osc::to_u8(self.fade.get());
+        let blur = osc::to_u8(self.blur.get());
+        if fade == 0 && blur == 0 {
+            return;
+        }
+        for row in 0..self.shape.rows() {
+            let len = self.shape.row_len(row);
+            let row = self.rows.get_mut(row);
+            if fade > 0 {
+                for led in 0..len {
+                    let pixel = row.get_mut(led);
+                    *pixel = pixel.fade_to_black_by(fade);
+                }
+            }
+            if blur > 0 {
+                blur_row(row, len, blur);
+            }
+        }
+    }
+}
+
+/// Spreads a share of each LED to its neighbors, like FastLED's `blur1d`
+fn blur_row(row: &mut impl Storage<Value = Hsv>, len: u8, amount: u8) {
+    let keep = 255 - amount;
+    let seep = amount >> 1;
+    let mut carry = Hsv::new(0, 0, 0);
+    for led in 0..len {
+        let mut color = *row.get(led);
+        let part = color.fade_to_black_by(255 - seep);
+        color = color.fade_to_black_by(255 - keep);
+        color = Blend::Add.apply(color, carry);
+        if led > 0 {
+            let previous = row.get_mut(led - 1);
+            *previous = Blend::Add.apply(*previous, part);
+        }
+        row.set(led, color);
+        carry = part;
+    }
+}
+
+impl<Fade, Blur, Inner, Rows> Pattern for FrameBuffer<Fade, Blur, Inner, Rows>
+where
+    Fade: osc::Oscillator,
+    Blur: osc::Oscillator,
+    Inner: Painter,
+    Rows: Storage,
+    Rows::Value: Storage<Value = Hsv>,
+{
+    fn tick(&mut self) {
+        self.fade.tick();
+        self.blur.tick();
+        self.inner.tick();
+
+        self.decay();
+
+        let mut canvas = Canvas {
+            rows: &mut self.rows,
+            shape: self.shape,
+        };
+        self.inner.paint(&mut canvas);
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        *self.rows.get(spine.index).get(led.index)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        *self
+            .rows
+            .get(self.shape.tip_row(spine.index))
+            .get(led.index)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        *self.rows.get(self.shape.arc_row(arc.index)).get(led.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{osc::Constant, star::FixedLayout};
+
+    const BLACK: Hsv = Hsv::new(0, 0, 0);
+    const WHITE: Hsv = Hsv::new(0, 0, 255);
+
+    type Rows = [[Hsv; 8]; 6];
+
+    fn rows() -> Rows {
+        [[BLACK; 8]; 6]
+    }
+
+    /// A single dot moving out along every spine, one LED per tick
+    struct Comet {
+        position: u8,
+    }
+
+    impl Painter for Comet {
+        fn tick(&mut self) {
+            self.position += 1;
+        }
+
+        fn paint<Rows>(&mut self, canvas: &mut Canvas<'_, Rows>)
+        where
+            Rows: Storage,
+            Rows::Value: Storage<Value = Hsv>,
+        {
+            if self.position < canvas.spine_len() {
+                for spine in 0..canvas.spines() {
+                    canvas.paint_spine(spine, self.position, WHITE);
+                }
+            }
+        }
+    }
+
+    fn spine(frame: &impl Pattern, led: u8) -> u8 {
+        frame.spine_color_at(Index::new(0, 2), Index::new(led, 8)).v
+    }
+
+    #[test]
+    fn test_comet_trail() {
+        let mut frame = FrameBuffer::new(
+            &FixedLayout::new(2, 2, 8, 1, 3),
+            Constant::<-64>,
+            Constant::<{ i8::MIN }>,
+            Comet { position: 0 },
+            rows(),
+        );
+        for _ in 0..5 {
+            frame.tick();
+        }
+
+        // The head is bright and the trail fades behind it
+        assert_eq!(spine(&frame, 5), 255);
+        for led in 1..5 {
+            assert!(spine(&frame, led) > 0);
+            assert!(spine(&frame, led) < spine(&frame, led + 1));
+        }
+        assert_eq!(spine(&frame, 6), 0);
+    }
+
+    #[test]
+    fn test_no_fade_keeps_frame() {
+        let mut frame = FrameBuffer::new(
+            &FixedLayout::new(2, 2, 8, 1, 3),
+            Constant::<{ i8::MIN }>,
+            Constant::<{ i8::MIN }>,
+            Comet { position: 0 },
+            rows(),
+        );
+        for _ in 0..20 {
+            frame.tick();
+        }
+        for led in 1..8 {
+            assert_eq!(spine(&frame, led), 255);
+        }
+
+        frame.clear();
+        assert_eq!(spine(&frame, 3), 0);
+    }
+
+    #[test]
+    fn test_blur_spreads() {
+        let mut row = [BLACK; 5];
+        row[2] = WHITE;
+        blur_row(&mut row, 5, 128);
+
+        assert!(row[2].v > row[1].v);
+        assert!(row[1].v > 0);
+        assert_eq!(row[1].v, row[3].v);
+        assert_eq!(row[0].v, 0);
+    }
+
+    #[test]
+    fn test_stamp_afterglow() {
+        let mut frame = FrameBuffer::new(
+            &FixedLayout::new(2, 2, 8, 1, 3),
+            Constant::<0>,
+            Constant::<{ i8::MIN }>,
+            Stamp::new(WHITE),
+            rows(),
+        );
+        frame.tick();
+        assert_eq!(spine(&frame, 0), 255);
+        assert_eq!(
+            frame.arc_color_at(Index::new(1, 2), Index::new(2, 3)).v,
+            255
+        );
+        assert_eq!(
+            frame
+                .spine_tip_color_at(Index::new(1, 2), Index::new(0, 1))
+                .v,
+            255
+        );
+
+        // Swap the stamp for something dark and the old frame lingers
+        let mut frame = FrameBuffer {
+            inner: Stamp::new(BLACK),
+            fade: frame.fade,
+            blur: frame.blur,
+            rows: frame.rows,
+            shape: frame.shape,
+        };
+        frame.tick();
+        let glow = spine(&frame, 0);
+        assert!(glow > 0 && glow < 255, "{glow}");
+    }
+
+    #[test]
+    #[should_panic(expected = "FrameBuffer row 2 needs 3 LEDs, but only 2 were provided")]
+    fn test_short_row() {
+        let mut rows = vec![vec![BLACK; 8]; 6];
+        rows[2].truncate(2);
+        FrameBuffer::new(
+            &FixedLayout::new(2, 2, 8, 1, 3),
+            Constant::<0>,
+            Constant::<0>,
+            Stamp::new(WHITE),
+            rows,
+        );
+    }
+}
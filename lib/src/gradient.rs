@@ -0,0 +1,337 @@
+use crate::{
+    color::{Hsv, lerp8},
+    osc,
+    pattern::{Index, Pattern, Seek},
+    star::Layout,
+};
+
+/// The color channel a gradient writes into
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    Hue,
+    Saturation,
+    Value,
+}
+
+/// The direction a gradient runs across the star
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    /// From the center of each spine out to its tip
+    ///
+    /// Tips sit at the end of the gradient. Arcs are passed through unchanged.
+    Spine,
+    /// Around the star, from spine 0 to the last spine
+    ///
+    /// Arcs continue the gradient between the spines they connect.
+    Around,
+    /// Along each arc, from the spine it starts at to the next one
+    ///
+    /// Spines and tips are passed through unchanged.
+    Arc,
+}
+
+/// Overwrites one channel of the inner pattern with a gradient
+///
+/// The `start` and `end` oscillators give the channel value (scaled to 0..255) at each end of the
+/// axis. Driving them with oscillators moves the gradient over time. Hue gradients always travel
+/// forward around the color wheel, wrapping from `start` to `end`.
+pub struct Gradient<Start, End, Inner>
+where
+    Start: osc::Oscillator,
+    End: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub axis: Axis,
+    pub channel: Channel,
+    pub start: Start,
+    pub end: End,
+    pub inner: Inner,
+}
+
+impl<Start, End, Inner> Gradient<Start, End, Inner>
+where
+    Start: osc::Oscillator,
+    End: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub fn new(axis: Axis, channel: Channel, start: Start, end: End, inner: Inner) -> Self {
+        Self {
+            axis,
+            channel,
+            start,
+            end,
+            inner,
+        }
+    }
+
+    pub fn hue(axis: Axis, start: Start, end: End, inner: Inner) -> Self {
+        Self::new(axis, Channel::Hue, start, end, inner)
+    }
+
+    pub fn saturation(axis: Axis, start: Start, end: End, inner: Inner) -> Self {
+        Self::new(axis, Channel::Saturation, start, end, inner)
+    }
+
+    pub fn value(axis: Axis, start: Start, end: End, inner: Inner) -> Self {
+        Self::new(axis, Channel::Value, start, end, inner)
+    }
+
+    #[inline(always)]
+    fn apply(&self, mut color: Hsv, position: Option<u8>) -> Hsv {
+        let Some(position) = position else {
+            return color;
+        };
+
+        let start = osc::to_u8(self.start.get());
+        let end = osc::to_u8(self.end.get());
+
+        match self.channel {
+            Channel::Hue => {
+                let span = end.wrapping_sub(start) as u16;
+                color.h = start.wrapping_add((span * position as u16 / 255) as u8);
+            }
+            Channel::Saturation => color.s = lerp8(start, end, position),
+            Channel::Value => color.v = lerp8(start, end, position),
+        }
+
+        color
+    }
+
+    /// Position of a spine around the star, normalized to 0..256
+    #[inline(always)]
+    fn around(spine: Index) -> u8 {
+        if spine.total == 0 {
+            return 0;
+        }
+        (spine.index as u16 * 256 / spine.total as u16) as u8
+    }
+}
+
+impl<Start, End, Inner> Pattern for Gradient<Start, End, Inner>
+where
+    Start: osc::Oscillator,
+    End: osc::Oscillator,
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.start.tick();
+        self.end.tick();
+        self.inner.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let position = match self.axis {
+            Axis::Spine => Some(led.fraction()),
+            Axis::Around => Some(Self::around(spine)),
+            Axis::Arc => None,
+        };
+        self.apply(self.inner.spine_color_at(spine, led), position)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let position = match self.axis {
+            Axis::Spine => Some(u8::MAX),
+            Axis::Around => Some(Self::around(spine)),
+            Axis::Arc => None,
+        };
+        self.apply(self.inner.spine_tip_color_at(spine, led), position)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let position = match self.axis {
+            Axis::Spine => None,
+            Axis::Around => {
+                // Place the arc LEDs evenly between the two spines it connects
+                let step = 256 / arc.total.max(1) as u16;
+                let offset = (led.index as u16 + 1) * step / (led.total as u16 + 1);
+                Some(Self::around(arc).wrapping_add(offset as u8))
+            }
+            Axis::Arc => Some(led.fraction()),
+        };
+        self.apply(self.inner.arc_color_at(arc, led), position)
+    }
+}
+
+impl<Start, End, Inner> Seek for Gradient<Start, End, Inner>
+where
+    Start: osc::Seek,
+    End: osc::Seek,
+    Inner: Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.start.seek(t);
+        self.end.seek(t);
+        self.inner.seek(t);
+    }
+}
+
+/// Fills each arc with a blend between the roots of the two spines it connects
+///
+/// Spines and tips show the source pattern as is, while arc `n` fades from the color at the root
+/// of spine `n` to the root of spine `n + 1`, so the center reads as one continuous color field.
+pub struct ArcBlend<Source>
+where
+    Source: Pattern,
+{
+    pub source: Source,
+    spines: u8,
+    spine_len: u8,
+}
+
+impl<Source> ArcBlend<Source>
+where
+    Source: Pattern,
+{
+    pub fn new(layout: &impl Layout, source: Source) -> Self {
+        Self {
+            source,
+            spines: layout.spines(),
+            spine_len: layout.spine_len_at(0),
+        }
+    }
+
+    #[inline(always)]
+    fn root(&self, spine: u8) -> Hsv {
+        let spine = Index::new(spine % self.spines.max(1), self.spines);
+        self.source
+            .spine_color_at(spine, Index::new(0, self.spine_len))
+    }
+}
+
+impl<Source> Pattern for ArcBlend<Source>
+where
+    Source: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.source.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.source.spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.source.spine_tip_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        // Arc LEDs sit between the spine roots rather than on top of them
+        let t = (led.index as u16 + 1) * 255 / (led.total as u16 + 1);
+        let from = self.root(arc.index);
+        let to = self.root(arc.index.wrapping_add(1));
+        from.lerp(to, t as u8)
+    }
+}
+
+impl<Source: Seek> Seek for ArcBlend<Source> {
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.source.seek(t);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        osc::{Constant, OscillatorExt as _, sawtooth},
+        star::FixedLayout,
+    };
+
+    const WHITE: Hsv = Hsv::new(0, 0, 255);
+
+    #[test]
+    fn test_spine_value_gradient() {
+        let pattern = Gradient::value(Axis::Spine, Constant::<-128>, Constant::<127>, WHITE);
+        let spine = Index::new(0, 4);
+
+        // Center is dark, the outermost LED is bright
+        assert_eq!(pattern.spine_color_at(spine, Index::new(0, 5)).v, 0);
+        assert_eq!(pattern.spine_color_at(spine, Index::new(2, 5)).v, 127);
+        assert_eq!(pattern.spine_color_at(spine, Index::new(4, 5)).v, 255);
+
+        // Tips sit at the end of the gradient
+        assert_eq!(pattern.spine_tip_color_at(spine, Index::new(0, 1)).v, 255);
+
+        // Arcs are untouched
+        assert_eq!(pattern.arc_color_at(spine, Index::new(0, 3)), WHITE);
+    }
+
+    #[test]
+    fn test_hue_gradient_wraps() {
+        // 192 -> 64 travels forward through 0
+        let pattern = Gradient::hue(Axis::Arc, Constant::<64>, Constant::<-64>, WHITE);
+        let arc = Index::new(0, 4);
+
+        assert_eq!(pattern.arc_color_at(arc, Index::new(0, 3)).h, 192);
+        assert_eq!(pattern.arc_color_at(arc, Index::new(1, 3)).h, 255);
+        assert_eq!(pattern.arc_color_at(arc, Index::new(2, 3)).h, 64);
+
+        // Spines are untouched
+        assert_eq!(pattern.spine_color_at(arc, Index::new(2, 3)), WHITE);
+    }
+
+    #[test]
+    fn test_around_gradient() {
+        let pattern = Gradient::saturation(Axis::Around, Constant::<-128>, Constant::<127>, WHITE);
+        let led = Index::new(0, 1);
+
+        let values: Vec<u8> = (0..4)
+            .map(|spine| pattern.spine_color_at(Index::new(spine, 4), led).s)
+            .collect();
+        assert_eq!(values, [0, 64, 128, 192]);
+
+        // Arc LEDs land between the spines they connect
+        let arc = pattern.arc_color_at(Index::new(1, 4), Index::new(0, 1)).s;
+        assert!(values[1] < arc && arc < values[2]);
+    }
+
+    #[test]
+    fn test_arc_blend() {
+        let layout = FixedLayout::new(4, 4, 3, 0, 3);
+        // Each spine root has its own brightness
+        let source = Gradient::value(Axis::Around, Constant::<-128>, Constant::<127>, WHITE);
+        let pattern = ArcBlend::new(&layout, source);
+        let root = |spine| {
+            pattern
+                .spine_color_at(Index::new(spine, 4), Index::new(0, 3))
+                .v
+        };
+
+        let arc: Vec<u8> = (0..3)
+            .map(|led| pattern.arc_color_at(Index::new(1, 4), Index::new(led, 3)).v)
+            .collect();
+        assert_eq!(arc, [79, 95, 111]);
+        assert!(root(1) < arc[0] && arc[2] < root(2));
+
+        // The last arc wraps back around to spine 0
+        let last = pattern.arc_color_at(Index::new(3, 4), Index::new(2, 3)).v;
+        assert!(last < root(3));
+    }
+
+    #[test]
+    fn test_moving_gradient() {
+        let mut pattern = Gradient::hue(
+            Axis::Spine,
+            sawtooth(),
+            sawtooth().add(Constant::<64>),
+            WHITE,
+        );
+        let spine = Index::new(0, 1);
+        let led = Index::new(0, 2);
+
+        let before = pattern.spine_color_at(spine, led).h;
+        pattern.tick();
+        let after = pattern.spine_color_at(spine, led).h;
+        assert_eq!(after, before.wrapping_add(1));
+    }
+}
@@ -0,0 +1,443 @@
+use crate::{
+    pattern::{Index, Surface},
+    star::{Cells, Layout},
+    storage::Storage,
+};
+
+/// A single logical LED on the star
+///
+/// `strip` is the spine, tip or arc the LED belongs to. Spine and tip LEDs are counted from the
+/// center out, and arc `n` runs from spine `n` to spine `n + 1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Node {
+    pub surface: Surface,
+    pub strip: u8,
+    pub led: u8,
+}
+
+impl Node {
+    #[inline(always)]
+    pub const fn new(surface: Surface, strip: u8, led: u8) -> Self {
+        Self {
+            surface,
+            strip,
+            led,
+        }
+    }
+}
+
+/// How the LEDs of the star connect to each other
+///
+/// LEDs are joined to their neighbors along each strip. The outer end of a spine joins its tip,
+/// and the center end joins the arcs on either side of it, so the arcs link neighboring spines
+/// together. Every spine, tip and arc is assumed to be the same length as the first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Graph {
+    cells: Cells,
+    spines: u8,
+    arcs: u8,
+    spine_len: u8,
+    tip_len: u8,
+    arc_len: u8,
+}
+
+impl Graph {
+    pub fn new(layout: &impl Layout) -> Self {
+        Self {
+            cells: Cells::new(layout),
+            spines: layout.spines(),
+            arcs: layout.arcs(),
+            spine_len: layout.spine_len_at(0),
+            tip_len: layout.tip_len_at(0),
+            arc_len: layout.arc_len_at(0),
+        }
+    }
+
+    /// Number of LEDs on a strip
+    #[inline(always)]
+    pub fn strip_len(&self, surface: Surface) -> u8 {
+        match surface {
+            Surface::Spine => self.spine_len,
+            Surface::Tip => self.tip_len,
+            Surface::Arc => self.arc_len,
+        }
+    }
+
+    /// Total number of LEDs in the graph
+    #[inline(always)]
+    pub fn cells(&self) -> u16 {
+        self.cells.len()
+    }
+
+    /// Dense index of a node, using the numbering from [`Cells`]
+    #[inline(always)]
+    pub fn cell(&self, node: Node) -> u16 {
+        let strips = match node.surface {
+            Surface::Arc => self.arcs,
+            Surface::Spine | Surface::Tip => self.spines,
+        };
+        let strip = Index::new(node.strip, strips);
+        let led = Index::new(node.led, self.strip_len(node.surface));
+        self.cells.at(node.surface, strip, led)
+    }
+
+    /// Returns the node at a dense index
+    #[inline]
+    pub fn node(&self, cell: u16) -> Node {
+        let (surface, position) = self.cells.locate(cell);
+        let len = self.strip_len(surface) as u16;
+        Node::new(surface, (position / len) as u8, (position % len) as u8)
+    }
+
+    /// Returns the LEDs directly connected to a node
+    pub fn neighbors(&self, node: Node) -> impl Iterator<Item = Node> + use<> {
+        let Node {
+            surface,
+            strip,
+            led,
+        } = node;
+        let len = self.strip_len(surface);
+        let mut neighbors = [None; 4];
+
+        // Neighbors along the same strip
+        if led > 0 {
+            neighbors[0] = Some(Node::new(surface, strip, led - 1));
+        }
+        if led + 1 < len {
+            neighbors[1] = Some(Node::new(surface, strip, led + 1));
+        }
+
+        match surface {
+            Surface::Spine => {
+                if led + 1 == len && self.tip_len > 0 {
+                    neighbors[2] = Some(Node::new(Surface::Tip, strip, 0));
+                }
+                if led == 0 && self.arc_len > 0 && self.arcs > 0 {
+                    if strip < self.arcs {
+                        neighbors[3] = Some(Node::new(Surface::Arc, strip, 0));
+                    }
+                    let previous = (strip + self.spines - 1) % self.spines;
+                    if previous < self.arcs {
+                        let last = self.arc_len - 1;
+                        neighbors[0] = Some(Node::new(Surface::Arc, previous, last));
+                    }
+                }
+            }
+            Surface::Tip => {
+                if led == 0 {
+                    let last = self.spine_len - 1;
+                    neighbors[0] = Some(Node::new(Surface::Spine, strip, last));
+                }
+            }
+            Surface::Arc => {
+                if led == 0 {
+                    neighbors[2] = Some(Node::new(Surface::Spine, strip, 0));
+                }
+                if led + 1 == len {
+                    let next = (strip + 1) % self.spines;
+                    neighbors[3] = Some(Node::new(Surface::Spine, next, 0));
+                }
+            }
+        }
+
+        neighbors.into_iter().flatten()
+    }
+
+    #[inline]
+    pub fn is_adjacent(&self, a: Node, b: Node) -> bool {
+        self.neighbors(a).any(|node| node == b)
+    }
+}
+
+/// Which way a path travels along a strip
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// From the first LED to the last: outward on spines and tips, towards the next spine on arcs
+    #[default]
+    Forward,
+    Backward,
+}
+
+/// A whole strip travelled in one direction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub surface: Surface,
+    pub strip: u8,
+    pub direction: Direction,
+}
+
+impl Segment {
+    #[inline(always)]
+    pub const fn new(surface: Surface, strip: u8, direction: Direction) -> Self {
+        Self {
+            surface,
+            strip,
+            direction,
+        }
+    }
+
+    /// From the center of a spine out to its tip
+    pub const fn spine_out(spine: u8) -> Self {
+        Self::new(Surface::Spine, spine, Direction::Forward)
+    }
+
+    /// From the tip of a spine in to the center
+    pub const fn spine_in(spine: u8) -> Self {
+        Self::new(Surface::Spine, spine, Direction::Backward)
+    }
+
+    /// Out along the tip of a spine
+    pub const fn tip_out(spine: u8) -> Self {
+        Self::new(Surface::Tip, spine, Direction::Forward)
+    }
+
+    /// Back along the tip of a spine
+    pub const fn tip_in(spine: u8) -> Self {
+        Self::new(Surface::Tip, spine, Direction::Backward)
+    }
+
+    /// Along arc `n` from spine `n` to spine `n + 1`
+    pub const fn arc(arc: u8) -> Self {
+        Self::new(Surface::Arc, arc, Direction::Forward)
+    }
+
+    /// Along arc `n` from spine `n + 1` back to spine `n`
+    pub const fn arc_back(arc: u8) -> Self {
+        Self::new(Surface::Arc, arc, Direction::Backward)
+    }
+
+    /// The LED at an offset from the start of the segment
+    #[inline(always)]
+    fn node(&self, len: u8, offset: u8) -> Node {
+        let led = match self.direction {
+            Direction::Forward => offset,
+            Direction::Backward => len - 1 - offset,
+        };
+        Node::new(self.surface, self.strip, led)
+    }
+
+    /// How far an LED is from the start of the segment
+    #[inline(always)]
+    fn offset(&self, len: u8, node: Node) -> Option<u8> {
+        if node.surface != self.surface || node.strip != self.strip || node.led >= len {
+            return None;
+        }
+        Some(match self.direction {
+            Direction::Forward => node.led,
+            Direction::Backward => len - 1 - node.led,
+        })
+    }
+}
+
+/// A continuous route across the star made of connected segments
+///
+/// Each segment has to start next to where the previous one ended, or on the same LED to turn
+/// back along it.
+///
+/// Positions count LEDs from the start of the first segment, so an animation can move along the
+/// path without caring which strip it is on. Empty strips are skipped.
+pub struct Path<Segments>
+where
+    Segments: Storage<Value = Segment>,
+{
+    graph: Graph,
+    segments: Segments,
+    len: u16,
+}
+
+impl<Segments> Path<Segments>
+where
+    Segments: Storage<Value = Segment>,
+{
+    /// Creates a path, checking each segment starts next to where the previous one ended
+    pub fn new(graph: Graph, segments: Segments) -> Self {
+        let mut len = 0u16;
+        let mut previous: Option<Node> = None;
+        for (index, segment) in segments.iter().enumerate() {
+            let segment_len = graph.strip_len(segment.surface);
+            if segment_len == 0 {
+                continue;
+            }
+            let first = segment.node(segment_len, 0);
+            if let Some(previous) = previous {
+                // Turning around on the same LED is allowed too
+                assert!(
+                    previous == first || graph.is_adjacent(previous, first),
+                    "Path segment {index} ({segment:?}) does not continue from {previous:?}"
+                );
+            }
+            previous = Some(segment.node(segment_len, segment_len - 1));
+            len += segment_len as u16;
+        }
+
+        Self {
+            graph,
+            segments,
+            len,
+        }
+    }
+
+    #[inline(always)]
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Total number of LEDs along the path
+    #[inline(always)]
+    pub fn len(&self) -> u16 {
+        self.len
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the LED at a position along the path
+    pub fn node_at(&self, mut position: u16) -> Option<Node> {
+        for segment in self.segments.iter() {
+            let len = self.graph.strip_len(segment.surface);
+            if position < len as u16 {
+                return Some(segment.node(len, position as u8));
+            }
+            position -= len as u16;
+        }
+        None
+    }
+
+    /// Returns every position along the path that passes through an LED
+    pub fn positions(&self, node: Node) -> impl Iterator<Item = u16> + '_ {
+        let mut start = 0u16;
+        self.segments.iter().filter_map(move |segment| {
+            let len = self.graph.strip_len(segment.surface);
+            let position = segment
+                .offset(len, node)
+                .map(|offset| start + offset as u16);
+            start += len as u16;
+            position
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::star::FixedLayout;
+
+    fn graph() -> Graph {
+        let layout = FixedLayout::new(4, 4, 5, 1, 3);
+        Graph::new(&layout)
+    }
+
+    fn neighbors(node: Node) -> Vec<Node> {
+        graph().neighbors(node).collect()
+    }
+
+    #[test]
+    fn test_spine_neighbors() {
+        // The center of a spine joins the arcs on either side
+        assert_eq!(
+            neighbors(Node::new(Surface::Spine, 0, 0)),
+            [
+                Node::new(Surface::Arc, 3, 2),
+                Node::new(Surface::Spine, 0, 1),
+                Node::new(Surface::Arc, 0, 0),
+            ]
+        );
+
+        // The outer end joins the tip
+        assert_eq!(
+            neighbors(Node::new(Surface::Spine, 2, 4)),
+            [
+                Node::new(Surface::Spine, 2, 3),
+                Node::new(Surface::Tip, 2, 0)
+            ]
+        );
+        assert_eq!(
+            neighbors(Node::new(Surface::Tip, 2, 0)),
+            [Node::new(Surface::Spine, 2, 4)]
+        );
+    }
+
+    #[test]
+    fn test_arcs_link_spines() {
+        let graph = graph();
+        for arc in 0..4 {
+            let next = (arc + 1) % 4;
+            assert!(graph.is_adjacent(
+                Node::new(Surface::Arc, arc, 0),
+                Node::new(Surface::Spine, arc, 0)
+            ));
+            assert!(graph.is_adjacent(
+                Node::new(Surface::Arc, arc, 2),
+                Node::new(Surface::Spine, next, 0)
+            ));
+        }
+
+        // Edges go both ways
+        for surface in [Surface::Spine, Surface::Tip, Surface::Arc] {
+            for strip in 0..4 {
+                for led in 0..graph.strip_len(surface) {
+                    let node = Node::new(surface, strip, led);
+                    for neighbor in graph.neighbors(node) {
+                        assert!(graph.is_adjacent(neighbor, node), "{neighbor:?} {node:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cells() {
+        let graph = graph();
+        assert_eq!(graph.cells(), 4 * 5 + 4 * 3 + 4);
+        for cell in 0..graph.cells() {
+            assert_eq!(graph.cell(graph.node(cell)), cell);
+        }
+        assert_eq!(graph.node(20), Node::new(Surface::Arc, 0, 0));
+        assert_eq!(graph.node(35), Node::new(Surface::Tip, 3, 0));
+    }
+
+    #[test]
+    fn test_path_across_arc() {
+        let path = Path::new(
+            graph(),
+            [
+                Segment::tip_in(0),
+                Segment::spine_in(0),
+                Segment::arc(0),
+                Segment::spine_out(1),
+                Segment::tip_out(1),
+            ],
+        );
+        assert_eq!(path.len(), 1 + 5 + 3 + 5 + 1);
+
+        assert_eq!(path.node_at(0), Some(Node::new(Surface::Tip, 0, 0)));
+        assert_eq!(path.node_at(1), Some(Node::new(Surface::Spine, 0, 4)));
+        assert_eq!(path.node_at(5), Some(Node::new(Surface::Spine, 0, 0)));
+        assert_eq!(path.node_at(6), Some(Node::new(Surface::Arc, 0, 0)));
+        assert_eq!(path.node_at(9), Some(Node::new(Surface::Spine, 1, 0)));
+        assert_eq!(path.node_at(15), None);
+
+        // Every step along the path moves to a neighboring LED
+        for position in 1..path.len() {
+            let a = path.node_at(position - 1).unwrap();
+            let b = path.node_at(position).unwrap();
+            assert!(path.graph().is_adjacent(a, b), "{a:?} {b:?}");
+            assert_eq!(path.positions(b).collect::<Vec<_>>(), [position]);
+        }
+    }
+
+    #[test]
+    fn test_path_revisits() {
+        let path = Path::new(graph(), [Segment::spine_out(2), Segment::spine_in(2)]);
+        let node = Node::new(Surface::Spine, 2, 1);
+        assert_eq!(path.positions(node).collect::<Vec<_>>(), [1, 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Path segment 1")]
+    fn test_disconnected_path() {
+        Path::new(graph(), [Segment::spine_out(0), Segment::arc(0)]);
+    }
+}
@@ -0,0 +1,406 @@
+use crate::{
+    color::Hsv,
+    pattern::{Index, Pattern},
+    star::Layout,
+    storage::Storage,
+};
+
+/// A way of splitting the spines into groups
+///
+/// Every spine belongs to exactly one group, and has a position among the other members of it.
+pub trait Grouping {
+    /// Number of groups
+    fn groups(&self) -> u8;
+
+    /// The group a spine belongs to, out of [`Grouping::groups`]
+    fn group(&self, spine: u8) -> u8;
+
+    /// A spine's position within its group, out of the group's size
+    fn member(&self, spine: u8) -> Index;
+
+    /// The spines in a group, in member order
+    fn spines(&self, group: u8) -> impl Iterator<Item = u8> + '_;
+}
+
+/// Groups of spines spread evenly around the star
+///
+/// Group `g` holds spines `g`, `g + groups`, `g + 2 * groups` and so on, so a 12-spine star has 6
+/// opposite pairs, 4 triads and 3 quads.
+#[derive(Clone, Copy, Debug)]
+pub struct Spaced {
+    groups: u8,
+    size: u8,
+}
+
+impl Spaced {
+    /// Splits the spines into groups of `size`, checking they divide evenly
+    pub fn new(layout: &impl Layout, size: u8) -> Self {
+        let spines = layout.spines();
+        assert!(
+            size > 0 && spines % size == 0,
+            "{spines} spines can't be split into evenly spaced groups of {size}"
+        );
+        Self {
+            groups: spines / size,
+            size,
+        }
+    }
+
+    /// Pairs of spines facing each other across the center
+    pub fn opposite(layout: &impl Layout) -> Self {
+        Self::new(layout, 2)
+    }
+
+    /// Sets of three spines, a third of a turn apart
+    pub fn triads(layout: &impl Layout) -> Self {
+        Self::new(layout, 3)
+    }
+
+    /// Sets of four spines, a quarter of a turn apart
+    pub fn quads(layout: &impl Layout) -> Self {
+        Self::new(layout, 4)
+    }
+}
+
+impl Grouping for Spaced {
+    #[inline(always)]
+    fn groups(&self) -> u8 {
+        self.groups
+    }
+
+    #[inline(always)]
+    fn group(&self, spine: u8) -> u8 {
+        spine % self.groups
+    }
+
+    #[inline(always)]
+    fn member(&self, spine: u8) -> Index {
+        Index::new(spine / self.groups, self.size)
+    }
+
+    fn spines(&self, group: u8) -> impl Iterator<Item = u8> + '_ {
+        (0..self.size).map(move |member| group + member * self.groups)
+    }
+}
+
+/// Runs of neighboring spines, such as the physical pairs a star is built from
+#[derive(Clone, Copy, Debug)]
+pub struct Adjacent {
+    groups: u8,
+    size: u8,
+}
+
+impl Adjacent {
+    /// Splits the spines into runs of `size`, checking they divide evenly
+    pub fn new(layout: &impl Layout, size: u8) -> Self {
+        let spines = layout.spines();
+        assert!(
+            size > 0 && spines % size == 0,
+            "{spines} spines can't be split into runs of {size}"
+        );
+        Self {
+            groups: spines / size,
+            size,
+        }
+    }
+
+    /// Neighboring spines paired up, starting with spines 0 and 1
+    pub fn pairs(layout: &impl Layout) -> Self {
+        Self::new(layout, 2)
+    }
+}
+
+impl Grouping for Adjacent {
+    #[inline(always)]
+    fn groups(&self) -> u8 {
+        self.groups
+    }
+
+    #[inline(always)]
+    fn group(&self, spine: u8) -> u8 {
+        spine / self.size
+    }
+
+    #[inline(always)]
+    fn member(&self, spine: u8) -> Index {
+        Index::new(spine % self.size, self.size)
+    }
+
+    fn spines(&self, group: u8) -> impl Iterator<Item = u8> + '_ {
+        let first = group * self.size;
+        first..first + self.size
+    }
+}
+
+/// Any assignment of spines to groups, with `assignments` holding the group of each spine
+///
+/// Each spine's position within its group is worked out up front and kept in `members`, so looking
+/// it up doesn't need to scan the other spines.
+#[derive(Clone, Copy, Debug)]
+pub struct Custom<Assignments, Members>
+where
+    Assignments: Storage<Value = u8>,
+    Members: Storage<Value = Index>,
+{
+    assignments: Assignments,
+    members: Members,
+    spines: u8,
+    groups: u8,
+}
+
+impl<Assignments, Members> Custom<Assignments, Members>
+where
+    Assignments: Storage<Value = u8>,
+    Members: Storage<Value = Index>,
+{
+    /// Creates the groups, checking there is an assignment for every spine and no group is empty
+    pub fn new(layout: &impl Layout, assignments: Assignments, members: Members) -> Self {
+        let spines = layout.spines();
+        assert!(
+            assignments.len() >= spines,
+            "Custom groups need an assignment for each of the {spines} spines, but only {} were provided",
+            assignments.len()
+        );
+        assert!(
+            members.len() >= spines,
+            "Custom groups need a member slot for each of the {spines} spines, but only {} were provided",
+            members.len()
+        );
+        let groups = (0..spines)
+            .map(|spine| {
+                let group = *assignments.get(spine);
+                group
+                    .checked_add(1)
+                    .expect("Custom group assignments must be below 255")
+            })
+            .max()
+            .unwrap_or(0);
+        let mut custom = Self {
+            assignments,
+            members,
+            spines,
+            groups,
+        };
+        for group in 0..groups {
+            let total = custom.spines(group).count() as u8;
+            assert!(total > 0, "Custom group {group} has no spines");
+            for index in 0..total {
+                let spine = custom.spines(group).nth(index as usize).unwrap();
+                custom.members.set(spine, Index::new(index, total));
+            }
+        }
+        custom
+    }
+}
+
+impl<Assignments, Members> Grouping for Custom<Assignments, Members>
+where
+    Assignments: Storage<Value = u8>,
+    Members: Storage<Value = Index>,
+{
+    #[inline(always)]
+    fn groups(&self) -> u8 {
+        self.groups
+    }
+
+    #[inline(always)]
+    fn group(&self, spine: u8) -> u8 {
+        *self.assignments.get(spine)
+    }
+
+    #[inline(always)]
+    fn member(&self, spine: u8) -> Index {
+        *self.members.get(spine)
+    }
+
+    fn spines(&self, group: u8) -> impl Iterator<Item = u8> + '_ {
+        (0..self.spines).filter(move |&spine| self.group(spine) == group)
+    }
+}
+
+/// Routes each group of spines to its own pattern
+///
+/// The inner pattern for a group sees each spine as its position within the group, so it can
+/// address "the second spine of the triad". Each arc goes with the spine it leaves from.
+pub struct PerGroup<G, V>
+where
+    G: Grouping,
+    V: Storage,
+    V::Value: Pattern,
+{
+    pub grouping: G,
+    pub values: V,
+}
+
+impl<G, V> PerGroup<G, V>
+where
+    G: Grouping,
+    V: Storage,
+    V::Value: Pattern,
+{
+    /// Creates a per-group pattern, checking there is a value for every group
+    pub fn new(grouping: G, values: V) -> Self {
+        assert!(
+            values.len() >= grouping.groups(),
+            "PerGroup needs a pattern for each of the {} groups, but only {} were provided",
+            grouping.groups(),
+            values.len()
+        );
+        Self { grouping, values }
+    }
+
+    #[inline(always)]
+    fn route(&self, spine: Index) -> (&V::Value, Index) {
+        let value = self.values.get(self.grouping.group(spine.index));
+        (value, self.grouping.member(spine.index))
+    }
+}
+
+impl<G, V> Pattern for PerGroup<G, V>
+where
+    G: Grouping,
+    V: Storage,
+    V::Value: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        for v in self.values.iter_mut() {
+            v.tick();
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let (value, member) = self.route(spine);
+        value.spine_color_at(member, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let (value, member) = self.route(spine);
+        value.spine_tip_color_at(member, led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let (value, member) = self.route(arc);
+        value.arc_color_at(member, led)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pattern::PerSpine, star::FixedLayout};
+
+    fn members(grouping: &impl Grouping, group: u8) -> Vec<u8> {
+        grouping.spines(group).collect()
+    }
+
+    /// Checks every spine is found in its own group at its member position
+    fn check(grouping: &impl Grouping) {
+        for spine in 0..12 {
+            let group = grouping.group(spine);
+            let member = grouping.member(spine);
+            assert!(group < grouping.groups());
+            assert_eq!(members(grouping, group)[member.index as usize], spine);
+            assert_eq!(members(grouping, group).len(), member.total as usize);
+        }
+    }
+
+    #[test]
+    fn test_spaced() {
+        let opposite = Spaced::opposite(&FixedLayout::new(12, 12, 4, 0, 2));
+        assert_eq!(opposite.groups(), 6);
+        assert_eq!(members(&opposite, 1), [1, 7]);
+        check(&opposite);
+
+        let triads = Spaced::triads(&FixedLayout::new(12, 12, 4, 0, 2));
+        assert_eq!(members(&triads, 2), [2, 6, 10]);
+        check(&triads);
+
+        let quads = Spaced::quads(&FixedLayout::new(12, 12, 4, 0, 2));
+        assert_eq!(members(&quads, 0), [0, 3, 6, 9]);
+        check(&quads);
+    }
+
+    #[test]
+    fn test_adjacent() {
+        let pairs = Adjacent::pairs(&FixedLayout::new(12, 12, 4, 0, 2));
+        assert_eq!(pairs.groups(), 6);
+        assert_eq!(members(&pairs, 3), [6, 7]);
+        check(&pairs);
+    }
+
+    #[test]
+    fn test_custom() {
+        let custom = Custom::new(
+            &FixedLayout::new(12, 12, 4, 0, 2),
+            [0, 0, 1, 2, 2, 2, 1, 1, 0, 2, 2, 2],
+            [Index::new(0, 0); 12],
+        );
+        assert_eq!(custom.groups(), 3);
+        assert_eq!(members(&custom, 1), [2, 6, 7]);
+        check(&custom);
+    }
+
+    #[test]
+    #[should_panic(expected = "Custom group 1 has no spines")]
+    fn test_custom_empty_group() {
+        Custom::new(
+            &FixedLayout::new(12, 12, 4, 0, 2),
+            [0, 0, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2],
+            [Index::new(0, 0); 12],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Custom group assignments must be below 255")]
+    fn test_custom_assignment_overflow() {
+        let mut assignments = [0u8; 12];
+        assignments[3] = 255;
+        Custom::new(
+            &FixedLayout::new(12, 12, 4, 0, 2),
+            assignments,
+            [Index::new(0, 0); 12],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "12 spines can't be split into evenly spaced groups of 5")]
+    fn test_uneven() {
+        Spaced::new(&FixedLayout::new(12, 12, 4, 0, 2), 5);
+    }
+
+    #[test]
+    fn test_per_group() {
+        let values = core::array::from_fn::<_, 4, _>(|v| Hsv::new(v as u8, 0, 255));
+        let pattern = PerGroup::new(Spaced::triads(&FixedLayout::new(12, 12, 4, 0, 2)), values);
+        let led = Index::new(0, 4);
+
+        let hues: Vec<u8> = (0..12)
+            .map(|spine| pattern.spine_color_at(Index::new(spine, 12), led).h)
+            .collect();
+        assert_eq!(hues, [0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3]);
+        assert_eq!(pattern.arc_color_at(Index::new(6, 12), led).h, 2);
+
+        // Each group's pattern sees its spines by member position
+        let values = core::array::from_fn::<_, 4, _>(|group| {
+            PerSpine::new(core::array::from_fn::<_, 3, _>(|member| {
+                Hsv::new((group * 10 + member) as u8, 0, 255)
+            }))
+        });
+        let pattern = PerGroup::new(Spaced::triads(&FixedLayout::new(12, 12, 4, 0, 2)), values);
+        assert_eq!(pattern.spine_color_at(Index::new(10, 12), led).h, 22);
+        assert_eq!(pattern.spine_tip_color_at(Index::new(5, 12), led).h, 11);
+    }
+
+    #[test]
+    #[should_panic(expected = "PerGroup needs a pattern for each of the 6 groups")]
+    fn test_per_group_too_few() {
+        PerGroup::new(
+            Spaced::opposite(&FixedLayout::new(12, 12, 4, 0, 2)),
+            [Hsv::new(0, 0, 0); 4],
+        );
+    }
+}
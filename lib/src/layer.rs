@@ -0,0 +1,264 @@
+use crate::{
+    color::Hsv,
+    osc,
+    pattern::{Index, Pattern, Seek},
+    tip::Arrivals,
+};
+
+/// How the top layer's color is combined with the base layer's color
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Blend {
+    /// The top color replaces the base color
+    #[default]
+    Normal,
+    /// Brightness adds (saturating), hue and saturation follow the brighter layer
+    Add,
+    /// Brightness screens (inverse multiply), hue and saturation follow the brighter layer
+    Screen,
+    /// Brightness multiplies, hue and saturation come from the base
+    Multiply,
+    /// The brighter of the two colors wins
+    Lighten,
+}
+
+impl Blend {
+    /// Combine a base and top color
+    #[inline]
+    pub fn apply(self, base: Hsv, top: Hsv) -> Hsv {
+        match self {
+            Blend::Normal => top,
+            Blend::Add => Self::mix(base, top, base.v.saturating_add(top.v)),
+            Blend::Screen => {
+                let v = 255 - Self::scale(255 - base.v, 255 - top.v);
+                Self::mix(base, top, v)
+            }
+            Blend::Multiply => Hsv::new(base.h, base.s, Self::scale(base.v, top.v)),
+            Blend::Lighten => {
+                if top.v >= base.v {
+                    top
+                } else {
+                    base
+                }
+            }
+        }
+    }
+
+    /// Scales `a` by `b / 255`, so that white (255) leaves `a` unchanged
+    #[inline(always)]
+    fn scale(a: u8, b: u8) -> u8 {
+        ((a as u16 * (b as u16 + 1)) >> 8) as u8
+    }
+
+    /// Weighs hue and saturation by each layer's share of the combined brightness
+    #[inline(always)]
+    fn mix(base: Hsv, top: Hsv, v: u8) -> Hsv {
+        let total = base.v as u16 + top.v as u16;
+        if total == 0 {
+            return Hsv::new(base.h, base.s, v);
+        }
+        let weight = (top.v as u16 * 255 / total) as u8;
+        let mut color = base.lerp(top, weight);
+        color.v = v;
+        color
+    }
+}
+
+/// Composites a top pattern over a base pattern on the same LEDs
+///
+/// The `opacity` oscillator (scaled to 0..255) fades between the base alone and the fully blended
+/// result. Both layers are ticked every frame.
+pub struct Layer<Base, Top, Opacity>
+where
+    Base: Pattern,
+    Top: Pattern,
+    Opacity: osc::Oscillator,
+{
+    pub base: Base,
+    pub top: Top,
+    pub blend: Blend,
+    pub opacity: Opacity,
+}
+
+impl<Base, Top, Opacity> Layer<Base, Top, Opacity>
+where
+    Base: Pattern,
+    Top: Pattern,
+    Opacity: osc::Oscillator,
+{
+    pub fn new(base: Base, top: Top, blend: Blend, opacity: Opacity) -> Self {
+        Self {
+            base,
+            top,
+            blend,
+            opacity,
+        }
+    }
+
+    #[inline(always)]
+    fn composite(&self, base: Hsv, top: Hsv) -> Hsv {
+        let blended = self.blend.apply(base, top);
+        base.lerp(blended, osc::to_u8(self.opacity.get()))
+    }
+}
+
+impl<Base, Top, Opacity> Pattern for Layer<Base, Top, Opacity>
+where
+    Base: Pattern,
+    Top: Pattern,
+    Opacity: osc::Oscillator,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.base.tick();
+        self.top.tick();
+        self.opacity.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.composite(
+            self.base.spine_color_at(spine, led),
+            self.top.spine_color_at(spine, led),
+        )
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.composite(
+            self.base.spine_tip_color_at(spine, led),
+            self.top.spine_tip_color_at(spine, led),
+        )
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.composite(
+            self.base.arc_color_at(arc, led),
+            self.top.arc_color_at(arc, led),
+        )
+    }
+}
+
+impl<Base, Top, Opacity> Seek for Layer<Base, Top, Opacity>
+where
+    Base: Seek,
+    Top: Seek,
+    Opacity: osc::Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.base.seek(t);
+        self.top.seek(t);
+        self.opacity.seek(t);
+    }
+}
+
+/// Arrivals come from the top layer, which usually carries the motion
+impl<Base, Top, Opacity> Arrivals for Layer<Base, Top, Opacity>
+where
+    Base: Pattern,
+    Top: Pattern + Arrivals,
+    Opacity: osc::Oscillator,
+{
+    #[inline(always)]
+    fn arrived(&self, spine: Index) -> bool {
+        self.top.arrived(spine)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        osc::{Constant, OscillatorExt as _, sawtooth, sine},
+        pattern::Oscillator,
+        streak::{StreakSpawner, StreakState},
+    };
+
+    const SPINE: Index = Index::new(0, 1);
+
+    #[test]
+    fn test_blend_modes() {
+        let base = Hsv::new(0, 255, 100);
+        let top = Hsv::new(100, 255, 200);
+
+        assert_eq!(Blend::Normal.apply(base, top), top);
+        assert_eq!(Blend::Lighten.apply(base, top), top);
+        assert_eq!(Blend::Lighten.apply(top, base), top);
+        assert_eq!(Blend::Multiply.apply(base, top), Hsv::new(0, 255, 78));
+
+        let add = Blend::Add.apply(base, top);
+        assert_eq!(add.v, 255);
+        // Hue leans towards the brighter layer
+        assert!(add.h > 50 && add.h < 100, "{add:?}");
+
+        let screen = Blend::Screen.apply(base, top);
+        assert!(screen.v > top.v && screen.v < 255, "{screen:?}");
+    }
+
+    #[test]
+    fn test_blend_black() {
+        let black = Hsv::new(0, 0, 0);
+        let color = Hsv::new(42, 128, 200);
+
+        // Black layers leave the other layer untouched when adding or screening
+        assert_eq!(Blend::Add.apply(black, color), color);
+        assert_eq!(Blend::Add.apply(color, black), color);
+        assert_eq!(Blend::Screen.apply(color, black), color);
+        assert_eq!(Blend::Screen.apply(black, color), color);
+        assert_eq!(Blend::Multiply.apply(color, black).v, 0);
+
+        // A white layer leaves the base untouched when multiplying
+        assert_eq!(Blend::Multiply.apply(color, Hsv::new(0, 0, 255)), color);
+    }
+
+    #[test]
+    fn test_layer_opacity() {
+        let base = Hsv::new(0, 0, 0);
+        let top = Hsv::new(0, 0, 255);
+        let led = Index::new(0, 1);
+
+        let hidden = Layer::new(base, top, Blend::Normal, Constant::<{ i8::MIN }>);
+        assert_eq!(hidden.spine_color_at(SPINE, led), base);
+
+        let half = Layer::new(base, top, Blend::Normal, Constant::<0>);
+        assert_eq!(half.spine_color_at(SPINE, led).v, 128);
+
+        let full = Layer::new(base, top, Blend::Normal, Constant::<{ i8::MAX }>);
+        assert_eq!(full.arc_color_at(SPINE, led), top);
+    }
+
+    #[test]
+    fn test_streaks_over_background() {
+        // A slowly breathing background
+        let background = Oscillator {
+            h: Constant::<{ i8::MIN }>,
+            s: Constant::<127>,
+            v: sine().freq(Constant::<-128>),
+        };
+        let streaks = StreakSpawner::new(
+            sawtooth().saturating_sub(126),
+            Constant::<64>,
+            Constant::<64>,
+            Constant::<8>,
+            Hsv::new(128, 255, 255),
+            [StreakState::default(); 8],
+        );
+        let mut layer = Layer::new(background, streaks, Blend::Lighten, Constant::<127>);
+
+        let mut saw_background = false;
+        let mut saw_streak = false;
+        for _ in 0..512 {
+            layer.tick();
+            for led in 0..8 {
+                let color = layer.spine_color_at(SPINE, Index::new(led, 8));
+                saw_background |= color.h == 0 && color.v > 0;
+                saw_streak |= color.h == 128 && color.v > 0;
+            }
+        }
+
+        // The background shows through wherever the streaks are dark
+        assert!(saw_background);
+        assert!(saw_streak);
+    }
+}
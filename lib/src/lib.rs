@@ -9,14 +9,34 @@ macro_rules! assume {
     };
 }
 
+pub mod automaton;
+pub mod clock;
 pub mod color;
 pub mod config;
+pub mod fire;
+pub mod frame;
+pub mod gradient;
+pub mod graph;
+pub mod group;
+pub mod layer;
+pub mod lightning;
+pub mod mask;
+pub mod master;
 pub mod osc;
+pub mod param;
 pub mod pattern;
+pub mod playlist;
 pub mod rand;
+#[cfg(any(test, feature = "std"))]
+pub mod registry;
+pub mod ripple;
 pub mod slotmap;
 pub mod star;
 pub mod storage;
 pub mod streak;
+pub mod tip;
+pub mod transform;
+pub mod transition;
+pub mod twinkle;
 
 pub use pattern::*;
@@ -0,0 +1,294 @@
+use crate::{
+    color::{Hsv, scale8},
+    osc,
+    pattern::{Index, Pattern},
+    star::Layout,
+};
+
+/// Ticks a bolt takes to reach the tip of its spine
+const STRIKE_TICKS: u8 = 3;
+
+/// Most re-strikes that can follow a bolt
+const MAX_RESTRIKES: u8 = 3;
+
+/// Re-strikes can only happen once the flash has faded below this level
+const RESTRIKE_BELOW: u8 = 128;
+
+/// A single bolt, stored in 7 bytes
+#[derive(Clone, Copy, Debug, Default)]
+struct Bolt {
+    spine: u8,
+    /// Brightness of the flash, fading to 0 once the bolt is over
+    level: u8,
+    /// How far the bolt has reached along its spine, in LEDs
+    front: u8,
+    restrikes: u8,
+    /// Seeds the flicker along the bolt, changing with each re-strike
+    seed: u8,
+    /// Length of the branches into the arcs after and before the spine, in LEDs
+    branches: [u8; 2],
+}
+
+/// Uneven brightness along a bolt, from 96 to 255
+#[inline(always)]
+fn flicker(led: u8, seed: u8) -> u8 {
+    let mut x = led.wrapping_mul(29) ^ seed;
+    x = x.wrapping_mul(157).wrapping_add(seed);
+    x ^= x >> 3;
+    96 + scale8(x, 159)
+}
+
+/// Bolts of lightning striking out from the center along a random spine
+///
+/// A bolt strikes each time `trigger` rises above 0, reaching the tip within a few ticks and
+/// branching into the arcs on either side of its spine. As the flash fades it may re-strike up to
+/// three times before going dark.
+///
+/// - `decay` sets how quickly a flash fades, lasting ~16 ticks (-128) down to ~3 ticks (127)
+pub struct Lightning<Trigger, Decay, Inner>
+where
+    Trigger: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub trigger: Trigger,
+    pub decay: Decay,
+    pub inner: Inner,
+    bolt: Bolt,
+    spines: u8,
+    arcs: u8,
+    spine_len: u8,
+    arc_len: u8,
+    armed: bool,
+}
+
+impl<Trigger, Decay, Inner> Lightning<Trigger, Decay, Inner>
+where
+    Trigger: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub fn new(layout: &impl Layout, trigger: Trigger, decay: Decay, inner: Inner) -> Self {
+        Self {
+            trigger,
+            decay,
+            inner,
+            bolt: Bolt::default(),
+            spines: layout.spines(),
+            arcs: layout.arcs(),
+            spine_len: layout.spine_len_at(0),
+            arc_len: layout.arc_len_at(0),
+            armed: true,
+        }
+    }
+
+    /// Starts a new bolt along a random spine
+    fn strike(&mut self) {
+        let branch = || crate::rand::range_u8(1, self.arc_len.div_ceil(2).max(1));
+        self.bolt = Bolt {
+            spine: crate::rand::below_u16(self.spines as u16) as u8,
+            level: 255,
+            front: 0,
+            restrikes: crate::rand::range_u8(0, MAX_RESTRIKES),
+            seed: crate::rand::u8(),
+            branches: [branch(), branch()],
+        };
+    }
+
+    /// Brightness of the bolt at a distance along its spine or branch
+    #[inline(always)]
+    fn brightness(&self, led: u8) -> u8 {
+        scale8(self.bolt.level, flicker(led, self.bolt.seed))
+    }
+
+    #[inline(always)]
+    fn color(&self, mut color: Hsv, brightness: u8) -> Hsv {
+        color.v = scale8(color.v, brightness);
+        color
+    }
+}
+
+impl<Trigger, Decay, Inner> Pattern for Lightning<Trigger, Decay, Inner>
+where
+    Trigger: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+{
+    fn tick(&mut self) {
+        self.trigger.tick();
+        self.decay.tick();
+        self.inner.tick();
+
+        // Only strike on the rising edge so a held trigger makes a single bolt
+        let triggered = self.trigger.get() > 0;
+        if triggered && self.armed {
+            self.strike();
+        }
+        self.armed = !triggered;
+
+        let step = (osc::to_u8(self.decay.get()) >> 2) + 16;
+        let bolt = &mut self.bolt;
+        bolt.level = bolt.level.saturating_sub(step);
+        bolt.front = bolt
+            .front
+            .saturating_add(self.spine_len.div_ceil(STRIKE_TICKS))
+            .min(self.spine_len);
+
+        if bolt.level > 0
+            && bolt.level < RESTRIKE_BELOW
+            && bolt.restrikes > 0
+            && crate::rand::u8() < 128
+        {
+            bolt.level = 255;
+            bolt.restrikes -= 1;
+            bolt.seed = crate::rand::u8();
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let lit = spine.index == self.bolt.spine && led.index < self.bolt.front;
+        let brightness = if lit { self.brightness(led.index) } else { 0 };
+        self.color(self.inner.spine_color_at(spine, led), brightness)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let lit = spine.index == self.bolt.spine && self.bolt.front >= self.spine_len;
+        let brightness = if lit { self.bolt.level } else { 0 };
+        self.color(self.inner.spine_tip_color_at(spine, led), brightness)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        // Arc n leaves spine n, so the bolt branches into its own arc and the one before it
+        let before = if self.bolt.spine == 0 {
+            self.arcs - 1
+        } else {
+            self.bolt.spine - 1
+        };
+        let branch = if arc.index == self.bolt.spine {
+            Some((led.index, self.bolt.branches[0]))
+        } else if arc.index == before {
+            Some((led.total - 1 - led.index, self.bolt.branches[1]))
+        } else {
+            None
+        };
+
+        let brightness = match branch {
+            // Branches thin out towards their ends
+            Some((distance, length)) if distance < length => {
+                let falloff = ((length - distance) as u16 * 255 / length as u16) as u8;
+                scale8(self.brightness(distance), falloff)
+            }
+            _ => 0,
+        };
+        self.color(self.inner.arc_color_at(arc, led), brightness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{osc::Constant, star::FixedLayout};
+
+    const WHITE: Hsv = Hsv::new(0, 0, 255);
+
+    fn lit_spines(lightning: &impl Pattern) -> Vec<u8> {
+        (0..12)
+            .filter(|&spine| {
+                lightning
+                    .spine_color_at(Index::new(spine, 12), Index::new(0, 9))
+                    .v
+                    > 0
+            })
+            .collect()
+    }
+
+    fn lit_arcs(lightning: &impl Pattern) -> Vec<u8> {
+        (0..12)
+            .filter(|&arc| {
+                (0..6).any(|led| {
+                    lightning
+                        .arc_color_at(Index::new(arc, 12), Index::new(led, 6))
+                        .v
+                        > 0
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_strikes_out_from_center() {
+        crate::rand::seed(7);
+        let mut lightning = Lightning::new(
+            &FixedLayout::new(12, 12, 9, 1, 6),
+            Constant::<127>,
+            Constant::<0>,
+            WHITE,
+        );
+        assert!(lit_spines(&lightning).is_empty());
+
+        lightning.tick();
+        let spines = lit_spines(&lightning);
+        assert_eq!(spines.len(), 1);
+        let spine = Index::new(spines[0], 12);
+        let tip = Index::new(0, 1);
+
+        // The bolt grows out along a single spine and reaches the tip
+        let reach = |lightning: &Lightning<_, _, _>| {
+            (0..9)
+                .filter(|&led| lightning.spine_color_at(spine, Index::new(led, 9)).v > 0)
+                .count()
+        };
+        assert_eq!(reach(&lightning), 3);
+        assert_eq!(lightning.spine_tip_color_at(spine, tip).v, 0);
+        lightning.tick();
+        assert_eq!(reach(&lightning), 6);
+        lightning.tick();
+        assert_eq!(reach(&lightning), 9);
+        assert!(lightning.spine_tip_color_at(spine, tip).v > 0);
+
+        // It branches into the arcs on either side of its spine
+        let before = (spine.index + 11) % 12;
+        assert_eq!(lit_arcs(&lightning), {
+            let mut arcs = vec![spine.index, before];
+            arcs.sort();
+            arcs
+        });
+        let start = lightning.arc_color_at(Index::new(spine.index, 12), Index::new(0, 6));
+        let end = lightning.arc_color_at(Index::new(before, 12), Index::new(5, 6));
+        assert!(start.v > 0 && end.v > 0);
+    }
+
+    #[test]
+    fn test_fades_after_restrikes() {
+        crate::rand::seed(3);
+        // Held high, the trigger only strikes once
+        let mut lightning = Lightning::new(
+            &FixedLayout::new(12, 12, 9, 1, 6),
+            Constant::<127>,
+            Constant::<{ i8::MIN }>,
+            WHITE,
+        );
+
+        let mut brightest = Vec::new();
+        for _ in 0..128 {
+            lightning.tick();
+            brightest.push(lightning.bolt.level);
+        }
+        assert_eq!(lightning.bolt.level, 0);
+        assert!(lit_spines(&lightning).is_empty());
+        assert!(lit_arcs(&lightning).is_empty());
+
+        // Re-strikes brighten the flash again while it fades
+        let restrikes = brightest
+            .windows(2)
+            .filter(|pair| pair[1] > pair[0])
+            .count();
+        assert!(
+            (1..=MAX_RESTRIKES as usize).contains(&restrikes),
+            "{restrikes} re-strikes"
+        );
+    }
+}
@@ -0,0 +1,358 @@
+use crate::{
+    color::Hsv,
+    osc,
+    pattern::{Index, Pattern},
+    storage::Storage,
+};
+
+/// Decides which LEDs belong to a region of the star
+pub trait Select {
+    fn tick(&mut self);
+    fn spine(&self, spine: Index, led: Index) -> bool;
+    fn tip(&self, spine: Index, led: Index) -> bool;
+    fn arc(&self, arc: Index, led: Index) -> bool;
+}
+
+/// A fixed region of the star
+///
+/// Regions made of whole spines include their tips, along with any arc that joins two of the
+/// selected spines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Region {
+    /// The spines whose bits are set, with spine 0 in the lowest bit
+    Spines(u16),
+    Even,
+    Odd,
+    /// The half of each spine nearest the center
+    Inner,
+    /// The half of each spine furthest from the center, along with the tips
+    Outer,
+    Tips,
+    Arcs,
+}
+
+impl Region {
+    #[inline(always)]
+    fn has_spine(self, spine: u8) -> bool {
+        match self {
+            Region::Spines(mask) => spine < 16 && mask & (1 << spine) != 0,
+            Region::Even => spine % 2 == 0,
+            Region::Odd => spine % 2 == 1,
+            _ => false,
+        }
+    }
+}
+
+/// Whether both spines joined by an arc are selected
+#[inline(always)]
+fn joins(arc: Index, has_spine: impl Fn(u8) -> bool) -> bool {
+    let next = if arc.index + 1 >= arc.total {
+        0
+    } else {
+        arc.index + 1
+    };
+    has_spine(arc.index) && has_spine(next)
+}
+
+impl Select for Region {
+    #[inline(always)]
+    fn tick(&mut self) {}
+
+    #[inline(always)]
+    fn spine(&self, spine: Index, led: Index) -> bool {
+        match self {
+            Region::Inner => led.index < led.total / 2,
+            Region::Outer => led.index >= led.total / 2,
+            Region::Tips | Region::Arcs => false,
+            _ => self.has_spine(spine.index),
+        }
+    }
+
+    #[inline(always)]
+    fn tip(&self, spine: Index, _led: Index) -> bool {
+        match self {
+            Region::Outer | Region::Tips => true,
+            Region::Inner | Region::Arcs => false,
+            _ => self.has_spine(spine.index),
+        }
+    }
+
+    #[inline(always)]
+    fn arc(&self, arc: Index, _led: Index) -> bool {
+        match self {
+            Region::Arcs => true,
+            Region::Inner | Region::Outer | Region::Tips => false,
+            _ => joins(arc, |spine| self.has_spine(spine)),
+        }
+    }
+}
+
+/// A window of neighboring spines that can be swept around the star
+///
+/// - `phase` sets where the window starts as a fraction of a full revolution, so a sawtooth sweeps
+///   it around once every 256 ticks
+/// - `width` sets how many spines are selected, from 1 (-128) to all of them (127)
+pub struct Sweep<Phase, Width>
+where
+    Phase: osc::Oscillator,
+    Width: osc::Oscillator,
+{
+    pub phase: Phase,
+    pub width: Width,
+}
+
+impl<Phase, Width> Sweep<Phase, Width>
+where
+    Phase: osc::Oscillator,
+    Width: osc::Oscillator,
+{
+    pub fn new(phase: Phase, width: Width) -> Self {
+        Self { phase, width }
+    }
+
+    #[inline(always)]
+    fn has_spine(&self, spine: u8, total: u8) -> bool {
+        let start = ((self.phase.get() as u8 as u16 * total as u16) >> 8) as u8;
+        let count = ((osc::to_u8(self.width.get()) as u16 * total as u16) >> 8) as u8 + 1;
+        let offset = if spine >= start {
+            spine - start
+        } else {
+            spine + total - start
+        };
+        offset < count
+    }
+}
+
+impl<Phase, Width> Select for Sweep<Phase, Width>
+where
+    Phase: osc::Oscillator,
+    Width: osc::Oscillator,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.phase.tick();
+        self.width.tick();
+    }
+
+    #[inline(always)]
+    fn spine(&self, spine: Index, _led: Index) -> bool {
+        self.has_spine(spine.index, spine.total)
+    }
+
+    #[inline(always)]
+    fn tip(&self, spine: Index, _led: Index) -> bool {
+        self.has_spine(spine.index, spine.total)
+    }
+
+    #[inline(always)]
+    fn arc(&self, arc: Index, _led: Index) -> bool {
+        joins(arc, |spine| self.has_spine(spine, arc.total))
+    }
+}
+
+/// Switches between a set of regions, with `choice` spread evenly across them
+pub struct Choose<Choice, Regions>
+where
+    Choice: osc::Oscillator,
+    Regions: Storage<Value = Region>,
+{
+    pub choice: Choice,
+    pub regions: Regions,
+}
+
+impl<Choice, Regions> Choose<Choice, Regions>
+where
+    Choice: osc::Oscillator,
+    Regions: Storage<Value = Region>,
+{
+    pub fn new(choice: Choice, regions: Regions) -> Self {
+        assert!(!regions.is_empty(), "Choose needs at least one region");
+        Self { choice, regions }
+    }
+
+    #[inline(always)]
+    fn region(&self) -> Region {
+        let len = self.regions.len() as u16;
+        let index = (osc::to_u8(self.choice.get()) as u16 * len) >> 8;
+        *self.regions.get(index as u8)
+    }
+}
+
+impl<Choice, Regions> Select for Choose<Choice, Regions>
+where
+    Choice: osc::Oscillator,
+    Regions: Storage<Value = Region>,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.choice.tick();
+    }
+
+    #[inline(always)]
+    fn spine(&self, spine: Index, led: Index) -> bool {
+        self.region().spine(spine, led)
+    }
+
+    #[inline(always)]
+    fn tip(&self, spine: Index, led: Index) -> bool {
+        self.region().tip(spine, led)
+    }
+
+    #[inline(always)]
+    fn arc(&self, arc: Index, led: Index) -> bool {
+        self.region().arc(arc, led)
+    }
+}
+
+/// Shows one pattern inside a region of the star and another everywhere else
+///
+/// Both patterns are ticked every frame, so they keep running while they are hidden.
+pub struct Mask<Selector, Inside, Outside>
+where
+    Selector: Select,
+    Inside: Pattern,
+    Outside: Pattern,
+{
+    pub selector: Selector,
+    pub inside: Inside,
+    pub outside: Outside,
+}
+
+impl<Selector, Inside, Outside> Mask<Selector, Inside, Outside>
+where
+    Selector: Select,
+    Inside: Pattern,
+    Outside: Pattern,
+{
+    pub fn new(selector: Selector, inside: Inside, outside: Outside) -> Self {
+        Self {
+            selector,
+            inside,
+            outside,
+        }
+    }
+}
+
+impl<Selector, Inside, Outside> Pattern for Mask<Selector, Inside, Outside>
+where
+    Selector: Select,
+    Inside: Pattern,
+    Outside: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.selector.tick();
+        self.inside.tick();
+        self.outside.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        if self.selector.spine(spine, led) {
+            self.inside.spine_color_at(spine, led)
+        } else {
+            self.outside.spine_color_at(spine, led)
+        }
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        if self.selector.tip(spine, led) {
+            self.inside.spine_tip_color_at(spine, led)
+        } else {
+            self.outside.spine_tip_color_at(spine, led)
+        }
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        if self.selector.arc(arc, led) {
+            self.inside.arc_color_at(arc, led)
+        } else {
+            self.outside.arc_color_at(arc, led)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osc::{Constant, sawtooth};
+
+    const LED: Index = Index::new(0, 4);
+
+    fn spines(selector: &impl Select) -> Vec<u8> {
+        (0..12)
+            .filter(|&spine| selector.spine(Index::new(spine, 12), LED))
+            .collect()
+    }
+
+    fn arcs(selector: &impl Select) -> Vec<u8> {
+        (0..12)
+            .filter(|&arc| selector.arc(Index::new(arc, 12), LED))
+            .collect()
+    }
+
+    #[test]
+    fn test_regions() {
+        let region = Region::Spines(0b1000_0000_0111);
+        assert_eq!(spines(&region), [0, 1, 2, 11]);
+        assert!(region.tip(Index::new(11, 12), LED));
+        assert_eq!(arcs(&region), [0, 1, 11]);
+
+        assert_eq!(spines(&Region::Odd), [1, 3, 5, 7, 9, 11]);
+        assert!(arcs(&Region::Even).is_empty());
+
+        let spine = Index::new(3, 12);
+        let leds = |region: Region| -> Vec<bool> {
+            (0..5)
+                .map(|led| region.spine(spine, Index::new(led, 5)))
+                .collect()
+        };
+        assert_eq!(leds(Region::Inner), [true, true, false, false, false]);
+        assert_eq!(leds(Region::Outer), [false, false, true, true, true]);
+        assert!(!Region::Inner.tip(spine, LED));
+        assert!(Region::Outer.tip(spine, LED));
+
+        assert!(spines(&Region::Tips).is_empty());
+        assert!(Region::Tips.tip(spine, LED));
+        assert_eq!(arcs(&Region::Arcs).len(), 12);
+        assert!(!Region::Arcs.tip(spine, LED));
+    }
+
+    #[test]
+    fn test_sweep() {
+        // A quarter of the way round, three spines wide
+        let sweep = Sweep::new(Constant::<64>, Constant::<-80>);
+        assert_eq!(spines(&sweep), [3, 4, 5]);
+        assert_eq!(arcs(&sweep), [3, 4]);
+
+        // The window wraps past the last spine
+        let mut sweep = Sweep::new(sawtooth(), Constant::<-80>);
+        for _ in 0..220 {
+            sweep.tick();
+        }
+        assert_eq!(spines(&sweep), [0, 10, 11]);
+        assert_eq!(arcs(&sweep), [10, 11]);
+    }
+
+    #[test]
+    fn test_choose() {
+        let choose = Choose::new(Constant::<{ i8::MIN }>, [Region::Even, Region::Odd]);
+        assert_eq!(spines(&choose)[0], 0);
+        let choose = Choose::new(Constant::<127>, [Region::Even, Region::Odd]);
+        assert_eq!(spines(&choose)[0], 1);
+    }
+
+    #[test]
+    fn test_mask() {
+        let inside = Hsv::new(1, 0, 255);
+        let outside = Hsv::new(2, 0, 255);
+        let mask = Mask::new(Region::Tips, inside, outside);
+        let spine = Index::new(0, 12);
+
+        assert_eq!(mask.spine_tip_color_at(spine, LED), inside);
+        assert_eq!(mask.spine_color_at(spine, LED), outside);
+        assert_eq!(mask.arc_color_at(spine, LED), outside);
+    }
+}
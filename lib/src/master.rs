@@ -0,0 +1,185 @@
+use crate::{
+    color::{Hsv, scale8},
+    osc::{self, FrequencyClock},
+    pattern::{Index, Pattern},
+};
+
+/// Global controls for a whole show, applied on top of any pattern
+///
+/// - `speed` runs the inner pattern at 0.25x (-128), the regular rate (0) or up to 4x (127)
+/// - `hue` rotates every color around the color wheel, with 0 leaving hues unchanged
+/// - `saturation` and `brightness` scale every color from none (-128) to unchanged (127)
+pub struct Master<Speed, Hue, Saturation, Brightness, Inner>
+where
+    Speed: osc::Oscillator,
+    Hue: osc::Oscillator,
+    Saturation: osc::Oscillator,
+    Brightness: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub speed: Speed,
+    pub hue: Hue,
+    pub saturation: Saturation,
+    pub brightness: Brightness,
+    pub inner: Inner,
+    clock: FrequencyClock,
+}
+
+impl<Speed, Hue, Saturation, Brightness, Inner> Master<Speed, Hue, Saturation, Brightness, Inner>
+where
+    Speed: osc::Oscillator,
+    Hue: osc::Oscillator,
+    Saturation: osc::Oscillator,
+    Brightness: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub fn new(
+        speed: Speed,
+        hue: Hue,
+        saturation: Saturation,
+        brightness: Brightness,
+        inner: Inner,
+    ) -> Self {
+        Self {
+            speed,
+            hue,
+            saturation,
+            brightness,
+            inner,
+            clock: FrequencyClock::default(),
+        }
+    }
+
+    #[inline(always)]
+    fn apply(&self, color: Hsv) -> Hsv {
+        Hsv {
+            h: color.h.wrapping_add(self.hue.get() as u8),
+            s: scale(color.s, self.saturation.get()),
+            v: scale(color.v, self.brightness.get()),
+        }
+    }
+}
+
+/// Scales a channel by an oscillator, leaving it untouched at the top of the range
+#[inline(always)]
+fn scale(value: u8, amount: osc::Value) -> u8 {
+    match osc::to_u8(amount) {
+        255 => value,
+        amount => scale8(value, amount),
+    }
+}
+
+impl<Speed, Hue, Saturation, Brightness, Inner> Pattern
+    for Master<Speed, Hue, Saturation, Brightness, Inner>
+where
+    Speed: osc::Oscillator,
+    Hue: osc::Oscillator,
+    Saturation: osc::Oscillator,
+    Brightness: osc::Oscillator,
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.speed.tick();
+        self.hue.tick();
+        self.saturation.tick();
+        self.brightness.tick();
+
+        let ticks = self.clock.tick(self.speed.get());
+        for _ in 0..ticks {
+            self.inner.tick();
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.apply(self.inner.spine_color_at(spine, led))
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.apply(self.inner.spine_tip_color_at(spine, led))
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.apply(self.inner.arc_color_at(arc, led))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osc::{Constant, sawtooth};
+
+    const LED: Index = Index::new(0, 1);
+
+    fn hue(pattern: &impl Pattern) -> u8 {
+        pattern.spine_color_at(LED, LED).h
+    }
+
+    fn rainbow() -> crate::pattern::Oscillator<osc::Sawtooth, Constant<127>, Constant<127>> {
+        crate::pattern::Oscillator {
+            h: sawtooth(),
+            s: Constant::<127>,
+            v: Constant::<127>,
+        }
+    }
+
+    #[test]
+    fn test_speed() {
+        let mut regular = Master::new(
+            Constant::<0>,
+            Constant::<0>,
+            Constant::<127>,
+            Constant::<127>,
+            rainbow(),
+        );
+        let mut fast = Master::new(
+            Constant::<127>,
+            Constant::<0>,
+            Constant::<127>,
+            Constant::<127>,
+            rainbow(),
+        );
+        let mut slow = Master::new(
+            Constant::<{ i8::MIN }>,
+            Constant::<0>,
+            Constant::<127>,
+            Constant::<127>,
+            rainbow(),
+        );
+        for _ in 0..20 {
+            regular.tick();
+            fast.tick();
+            slow.tick();
+        }
+        // The rainbow starts at hue 128
+        assert_eq!(hue(&regular), 128 + 20);
+        assert_eq!(hue(&fast), 128 + 80);
+        assert_eq!(hue(&slow), 128 + 5);
+    }
+
+    #[test]
+    fn test_color() {
+        let color = Hsv::new(250, 200, 100);
+        let unchanged = Master::new(
+            Constant::<0>,
+            Constant::<0>,
+            Constant::<127>,
+            Constant::<127>,
+            color,
+        );
+        assert_eq!(unchanged.spine_color_at(LED, LED), color);
+
+        let master = Master::new(
+            Constant::<0>,
+            Constant::<10>,
+            Constant::<0>,
+            Constant::<{ i8::MIN }>,
+            color,
+        );
+        assert_eq!(master.arc_color_at(LED, LED), Hsv::new(4, 100, 0));
+        assert_eq!(master.spine_tip_color_at(LED, LED).h, 4);
+    }
+}
@@ -3,11 +3,45 @@ mod lut {
         unsafe { core::mem::transmute::<&[u8; 64], &[i8; 64]>(include_bytes!("osc/sine.bin")) };
 }
 
+use crate::pattern::Index;
+
 pub type Value = i8;
 
+/// Convert an oscillator value (-128..127) to an unsigned level (0..255)
+#[inline(always)]
+pub const fn to_u8(value: Value) -> u8 {
+    (value as u8).wrapping_add(128)
+}
+
 pub trait Oscillator {
     fn tick(&mut self);
     fn get(&self) -> Value;
+
+    /// Returns the value at a spine or arc, which is the same everywhere unless overridden
+    #[inline(always)]
+    fn get_at(&self, _index: Index) -> Value {
+        self.get()
+    }
+}
+
+/// Oscillators whose value is a pure function of time
+///
+/// Time counts ticks from the oscillator's starting state, including any custom starting phase
+/// such as [`Sine::phase`].
+pub trait Seek: Oscillator {
+    /// Returns the value `t` ticks after the start, without changing the oscillator
+    fn value_at(&self, t: u32) -> Value;
+
+    /// Moves the oscillator to `t` ticks after the start
+    fn seek(&mut self, t: u32);
+}
+
+/// Oscillators that can compute their value some ticks ahead without ticking
+///
+/// This lets a single instance show a different phase at every spine, see [`Spread`].
+pub trait PhaseShift: Oscillator {
+    /// Returns the value the oscillator will have after another `ticks` ticks
+    fn get_shifted(&self, ticks: u8) -> Value;
 }
 
 macro_rules! impl_binary_ext {
@@ -50,11 +84,19 @@ pub trait OscillatorExt: Oscillator {
     impl_unary_ext!(neg, Neg);
 
     impl_binary_ext!(freq, WithFrequency);
+
+    #[inline(always)]
+    fn spread(self, spans: u8) -> Spread<Self>
+    where
+        Self: Sized + PhaseShift,
+    {
+        Spread::new(self, spans)
+    }
 }
 
 impl<T: Oscillator> OscillatorExt for T {}
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct Constant<const V: Value>;
 
 impl<const V: Value> Oscillator for Constant<V> {
@@ -67,6 +109,23 @@ impl<const V: Value> Oscillator for Constant<V> {
     }
 }
 
+impl<const V: Value> Seek for Constant<V> {
+    #[inline(always)]
+    fn value_at(&self, _t: u32) -> Value {
+        V
+    }
+
+    #[inline(always)]
+    fn seek(&mut self, _t: u32) {}
+}
+
+impl<const V: Value> PhaseShift for Constant<V> {
+    #[inline(always)]
+    fn get_shifted(&self, _ticks: u8) -> Value {
+        V
+    }
+}
+
 impl Oscillator for Value {
     #[inline(always)]
     fn tick(&mut self) {}
@@ -77,6 +136,23 @@ impl Oscillator for Value {
     }
 }
 
+impl Seek for Value {
+    #[inline(always)]
+    fn value_at(&self, _t: u32) -> Value {
+        *self
+    }
+
+    #[inline(always)]
+    fn seek(&mut self, _t: u32) {}
+}
+
+impl PhaseShift for Value {
+    #[inline(always)]
+    fn get_shifted(&self, _ticks: u8) -> Value {
+        *self
+    }
+}
+
 pub fn triangle() -> Triangle {
     Triangle::new()
 }
@@ -124,6 +200,61 @@ impl Oscillator for Triangle {
     }
 }
 
+/// Ticks in one full cycle of a [`Triangle`]
+const TRIANGLE_PERIOD: u16 = 2 * u8::MAX as u16;
+
+/// Where a fresh [`Triangle`] starts within its cycle, at 0 and rising
+const TRIANGLE_START: u16 = 128;
+
+impl Triangle {
+    /// Position within the cycle, rising from MIN for the first half and falling after
+    #[inline(always)]
+    fn position(&self) -> u16 {
+        if self.direction {
+            to_u8(self.counter) as u16
+        } else {
+            TRIANGLE_PERIOD - to_u8(self.counter) as u16
+        }
+    }
+
+    #[inline(always)]
+    fn value_at_position(position: u16) -> Value {
+        if position <= u8::MAX as u16 {
+            (position as u8).wrapping_sub(128) as Value
+        } else {
+            ((TRIANGLE_PERIOD - position) as u8).wrapping_sub(128) as Value
+        }
+    }
+
+    #[inline(always)]
+    fn position_at(t: u32) -> u16 {
+        ((TRIANGLE_START as u32 + t) % TRIANGLE_PERIOD as u32) as u16
+    }
+}
+
+impl Seek for Triangle {
+    #[inline]
+    fn value_at(&self, t: u32) -> Value {
+        Self::value_at_position(Self::position_at(t))
+    }
+
+    #[inline]
+    fn seek(&mut self, t: u32) {
+        let position = Self::position_at(t);
+        // The peaks belong to the half that just reached them, matching `tick`
+        self.direction = position < u8::MAX as u16;
+        self.counter = Self::value_at_position(position);
+    }
+}
+
+impl PhaseShift for Triangle {
+    #[inline]
+    fn get_shifted(&self, ticks: u8) -> Value {
+        let position = (self.position() + ticks as u16) % TRIANGLE_PERIOD;
+        Self::value_at_position(position)
+    }
+}
+
 pub fn sawtooth() -> Sawtooth {
     Sawtooth::new()
 }
@@ -151,6 +282,25 @@ impl Oscillator for Sawtooth {
     }
 }
 
+impl Seek for Sawtooth {
+    #[inline(always)]
+    fn value_at(&self, t: u32) -> Value {
+        t as u8 as Value
+    }
+
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.counter = self.value_at(t);
+    }
+}
+
+impl PhaseShift for Sawtooth {
+    #[inline(always)]
+    fn get_shifted(&self, ticks: u8) -> Value {
+        self.counter.wrapping_add(ticks as Value)
+    }
+}
+
 pub fn square<D: Oscillator>(duty_cycle: D) -> Square<D> {
     Square::new(duty_cycle)
 }
@@ -187,6 +337,34 @@ impl<D: Oscillator> Oscillator for Square<D> {
     }
 }
 
+impl<D: Seek> Seek for Square<D> {
+    #[inline(always)]
+    fn value_at(&self, t: u32) -> Value {
+        if (t as u8 as Value) < self.duty_cycle.value_at(t) {
+            Value::MIN
+        } else {
+            Value::MAX
+        }
+    }
+
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.counter = t as u8 as Value;
+        self.duty_cycle.seek(t);
+    }
+}
+
+impl<D: PhaseShift> PhaseShift for Square<D> {
+    #[inline(always)]
+    fn get_shifted(&self, ticks: u8) -> Value {
+        if self.counter.wrapping_add(ticks as Value) < self.duty_cycle.get_shifted(ticks) {
+            Value::MIN
+        } else {
+            Value::MAX
+        }
+    }
+}
+
 pub fn sine() -> Sine {
     Sine::new()
 }
@@ -194,15 +372,19 @@ pub fn sine() -> Sine {
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Sine {
     counter: u8,
+    start: u8,
 }
 
 impl Sine {
     pub fn new() -> Self {
-        Self { counter: 0 }
+        Self::phase(0)
     }
 
     pub fn phase(counter: u8) -> Self {
-        Self { counter }
+        Self {
+            counter,
+            start: counter,
+        }
     }
 }
 
@@ -214,12 +396,36 @@ impl Oscillator for Sine {
 
     #[inline(always)]
     fn get(&self) -> Value {
-        match self.counter {
-            0..64 => lut::SINE[self.counter as usize],
-            64..128 => lut::SINE[127 - self.counter as usize],
-            128..192 => -lut::SINE[self.counter as usize - 128],
-            _ => -lut::SINE[255 - self.counter as usize],
-        }
+        sine_at(self.counter)
+    }
+}
+
+impl Seek for Sine {
+    #[inline(always)]
+    fn value_at(&self, t: u32) -> Value {
+        sine_at(self.start.wrapping_add(t as u8))
+    }
+
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.counter = self.start.wrapping_add(t as u8);
+    }
+}
+
+impl PhaseShift for Sine {
+    #[inline(always)]
+    fn get_shifted(&self, ticks: u8) -> Value {
+        sine_at(self.counter.wrapping_add(ticks))
+    }
+}
+
+#[inline(always)]
+fn sine_at(counter: u8) -> Value {
+    match counter {
+        0..64 => lut::SINE[counter as usize],
+        64..128 => lut::SINE[127 - counter as usize],
+        128..192 => -lut::SINE[counter as usize - 128],
+        _ => -lut::SINE[255 - counter as usize],
     }
 }
 
@@ -242,17 +448,45 @@ impl<O: Oscillator> Oscillator for Neg<O> {
 
     #[inline(always)]
     fn get(&self) -> Value {
-        let value = self.inner.get();
-        if value == Value::MIN {
-            Value::MAX
-        } else {
-            -value
-        }
+        negate(self.inner.get())
+    }
+
+    #[inline(always)]
+    fn get_at(&self, index: Index) -> Value {
+        negate(self.inner.get_at(index))
+    }
+}
+
+impl<O: Seek> Seek for Neg<O> {
+    #[inline(always)]
+    fn value_at(&self, t: u32) -> Value {
+        negate(self.inner.value_at(t))
+    }
+
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.inner.seek(t);
+    }
+}
+
+impl<O: PhaseShift> PhaseShift for Neg<O> {
+    #[inline(always)]
+    fn get_shifted(&self, ticks: u8) -> Value {
+        negate(self.inner.get_shifted(ticks))
+    }
+}
+
+#[inline(always)]
+fn negate(value: Value) -> Value {
+    if value == Value::MIN {
+        Value::MAX
+    } else {
+        -value
     }
 }
 
 mod math {
-    use super::{Oscillator, Value};
+    use super::{Index, Oscillator, PhaseShift, Seek, Value};
 
     macro_rules! impl_math {
         ($name:ident, $op:ident) => {
@@ -280,6 +514,39 @@ mod math {
                 fn get(&self) -> Value {
                     self.0.get().$op(self.1.get())
                 }
+
+                #[inline(always)]
+                fn get_at(&self, index: Index) -> Value {
+                    self.0.get_at(index).$op(self.1.get_at(index))
+                }
+            }
+
+            impl<A, B> Seek for $name<A, B>
+            where
+                A: Seek,
+                B: Seek,
+            {
+                #[inline(always)]
+                fn value_at(&self, t: u32) -> Value {
+                    self.0.value_at(t).$op(self.1.value_at(t))
+                }
+
+                #[inline(always)]
+                fn seek(&mut self, t: u32) {
+                    self.0.seek(t);
+                    self.1.seek(t);
+                }
+            }
+
+            impl<A, B> PhaseShift for $name<A, B>
+            where
+                A: PhaseShift,
+                B: PhaseShift,
+            {
+                #[inline(always)]
+                fn get_shifted(&self, ticks: u8) -> Value {
+                    self.0.get_shifted(ticks).$op(self.1.get_shifted(ticks))
+                }
             }
         };
     }
@@ -299,6 +566,59 @@ mod math {
 
 pub use math::*;
 
+/// Spreads the phase of a single oscillator evenly around the star
+///
+/// Index `i` of `total` reads the oscillator `i * 256 * spans / total` ticks ahead, wrapping every
+/// 256 ticks. A 256-tick oscillator such as a sawtooth hue goes through `spans` full cycles
+/// around the star, while a [`Triangle`], with its 510-tick period, only gets through half a cycle
+/// per span. Only one copy of the oscillator's state is kept however many spines read it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Spread<O> {
+    pub inner: O,
+    /// Number of 256-tick spans covered around the star
+    pub spans: u8,
+}
+
+impl<O: PhaseShift> Spread<O> {
+    pub fn new(inner: O, spans: u8) -> Self {
+        Self { inner, spans }
+    }
+}
+
+impl<O: PhaseShift + Seek> Seek for Spread<O> {
+    #[inline(always)]
+    fn value_at(&self, t: u32) -> Value {
+        self.inner.value_at(t)
+    }
+
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.inner.seek(t);
+    }
+}
+
+impl<O: PhaseShift> Oscillator for Spread<O> {
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.inner.tick();
+    }
+
+    /// Returns the value at index 0
+    #[inline(always)]
+    fn get(&self) -> Value {
+        self.inner.get()
+    }
+
+    #[inline(always)]
+    fn get_at(&self, index: Index) -> Value {
+        if index.total == 0 {
+            return self.inner.get();
+        }
+        let ticks = index.index as u32 * 256 * self.spans as u32 / index.total as u32;
+        self.inner.get_shifted(ticks as u8)
+    }
+}
+
 pub const fn rng() -> Rng {
     Rng
 }
@@ -320,6 +640,135 @@ impl Oscillator for Rng {
     }
 }
 
+/// Scale a centered sample in -128..127 to `mean ± spread`
+#[inline(always)]
+fn distribute(mean: Value, spread: Value, sample: Value) -> Value {
+    let offset = (sample as i16 * spread as i16) >> 7;
+    (mean as i16 + offset).clamp(Value::MIN as i16, Value::MAX as i16) as Value
+}
+
+pub fn rng_triangular<Mean: Oscillator, Spread: Oscillator>(
+    mean: Mean,
+    spread: Spread,
+) -> RngTriangular<Mean, Spread> {
+    RngTriangular::new(mean, spread)
+}
+
+/// Triangular random oscillator
+/// Returns a random value in `mean ± spread` on each get(), most likely near the mean
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RngTriangular<Mean, Spread> {
+    mean: Mean,
+    spread: Spread,
+}
+
+impl<Mean, Spread> RngTriangular<Mean, Spread> {
+    pub fn new(mean: Mean, spread: Spread) -> Self {
+        Self { mean, spread }
+    }
+}
+
+impl<Mean, Spread> Oscillator for RngTriangular<Mean, Spread>
+where
+    Mean: Oscillator,
+    Spread: Oscillator,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.mean.tick();
+        self.spread.tick();
+    }
+
+    #[inline(always)]
+    fn get(&self) -> Value {
+        let sample = crate::rand::triangular_i8();
+        distribute(self.mean.get(), self.spread.get(), sample)
+    }
+}
+
+pub fn rng_normal<Mean: Oscillator, Spread: Oscillator>(
+    mean: Mean,
+    spread: Spread,
+) -> RngNormal<Mean, Spread> {
+    RngNormal::new(mean, spread)
+}
+
+/// Approximately normal (Gaussian) random oscillator
+/// Returns a random value in `mean ± spread` on each get(), with σ ≈ spread / 3.5
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RngNormal<Mean, Spread> {
+    mean: Mean,
+    spread: Spread,
+}
+
+impl<Mean, Spread> RngNormal<Mean, Spread> {
+    pub fn new(mean: Mean, spread: Spread) -> Self {
+        Self { mean, spread }
+    }
+}
+
+impl<Mean, Spread> Oscillator for RngNormal<Mean, Spread>
+where
+    Mean: Oscillator,
+    Spread: Oscillator,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.mean.tick();
+        self.spread.tick();
+    }
+
+    #[inline(always)]
+    fn get(&self) -> Value {
+        let sample = crate::rand::normal_i8();
+        distribute(self.mean.get(), self.spread.get(), sample)
+    }
+}
+
+pub const fn rng_weighted<const N: usize>(table: [(Value, u8); N]) -> RngWeighted<N> {
+    RngWeighted::new(table)
+}
+
+/// Weighted-table random oscillator
+/// Returns one of the table's values on each get(), picked in proportion to its weight
+#[derive(Clone, Copy, Debug)]
+pub struct RngWeighted<const N: usize> {
+    table: [(Value, u8); N],
+}
+
+impl<const N: usize> RngWeighted<N> {
+    pub const fn new(table: [(Value, u8); N]) -> Self {
+        const {
+            if N == 0 {
+                panic!("Cannot create a weighted table of length 0");
+            }
+        }
+        Self { table }
+    }
+}
+
+impl<const N: usize> Oscillator for RngWeighted<N> {
+    #[inline(always)]
+    fn tick(&mut self) {
+        // No-op, randomness happens on get()
+    }
+
+    fn get(&self) -> Value {
+        // Summed as u32 so large tables can't overflow
+        let total: u32 = self.table.iter().map(|(_, weight)| *weight as u32).sum();
+        let mut pick = crate::rand::below_u32(total);
+        for (value, weight) in self.table.iter() {
+            let weight = *weight as u32;
+            if pick < weight {
+                return *value;
+            }
+            pick -= weight;
+        }
+        // Only reachable when all of the weights are 0
+        self.table[0].0
+    }
+}
+
 pub fn random_pulse<Min: Oscillator, Max: Oscillator>(
     min_count: Min,
     max_count: Max,
@@ -409,15 +858,22 @@ where
     fn get(&self) -> Value {
         self.inner.get()
     }
+
+    fn get_at(&self, index: Index) -> Value {
+        self.inner.get_at(index)
+    }
 }
 
+/// Turns a frequency into a whole number of ticks per call, carrying the fraction over
+///
+/// A frequency of 0 is the regular rate, 127 is 4x and -128 is 0.25x.
 #[derive(Clone, Copy, Debug, Default)]
-struct FrequencyClock {
+pub(crate) struct FrequencyClock {
     frac: u8,
 }
 
 impl FrequencyClock {
-    fn tick(&mut self, freq: Value) -> u8 {
+    pub(crate) fn tick(&mut self, freq: Value) -> u8 {
         // If the frequency is set to 0 then it's the regular rate
         if freq == 0 {
             return 1;
@@ -470,6 +926,81 @@ impl FrequencyClock {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_to_u8() {
+        assert_eq!(to_u8(Value::MIN), 0);
+        assert_eq!(to_u8(-1), 127);
+        assert_eq!(to_u8(0), 128);
+        assert_eq!(to_u8(Value::MAX), 255);
+    }
+
+    /// Checks that shifting matches actually ticking a copy of the oscillator
+    fn check_shift<O: PhaseShift + Clone>(mut osc: O) {
+        for _ in 0..600 {
+            let mut ahead = osc.clone();
+            for ticks in 0..=255u8 {
+                assert_eq!(osc.get_shifted(ticks), ahead.get(), "{ticks} ticks ahead");
+                ahead.tick();
+            }
+            osc.tick();
+        }
+    }
+
+    #[test]
+    fn test_phase_shift() {
+        check_shift(sawtooth());
+        check_shift(triangle());
+        check_shift(sine());
+        check_shift(square(Constant::<-64>));
+        check_shift(sawtooth().add(Constant::<10>).neg());
+    }
+
+    /// Checks that seeking matches ticking a fresh oscillator
+    fn check_seek<O: Seek>(fresh: impl Fn() -> O) {
+        let mut ticked = fresh();
+        let mut sought = fresh();
+        for t in 0..1200 {
+            assert_eq!(sought.value_at(t), ticked.get(), "value at {t}");
+            sought.seek(t);
+            assert_eq!(sought.get(), ticked.get(), "seek to {t}");
+            ticked.tick();
+        }
+
+        // Seeking backwards and then ticking carries on from there
+        sought.seek(300);
+        sought.tick();
+        assert_eq!(sought.get(), fresh().value_at(301));
+    }
+
+    #[test]
+    fn test_seek() {
+        check_seek(sawtooth);
+        check_seek(triangle);
+        check_seek(sine);
+        check_seek(|| Sine::phase(64));
+        check_seek(|| square(triangle()));
+        check_seek(|| sine().saturating_add(Constant::<10>).neg());
+    }
+
+    #[test]
+    fn test_spread() {
+        let mut osc = sawtooth().spread(1);
+        osc.tick();
+        let at = |osc: &Spread<Sawtooth>, index| osc.get_at(Index::new(index, 4));
+        assert_eq!(osc.get(), 1);
+        assert_eq!(at(&osc, 0), 1);
+        assert_eq!(at(&osc, 1), 65);
+        assert_eq!(at(&osc, 3), 1i8.wrapping_add(192u8 as i8));
+
+        // Two spans of a sawtooth repeat halfway around
+        let osc = sawtooth().spread(2);
+        assert_eq!(at(&osc, 2), at(&osc, 0));
+
+        // Combinators keep the spread
+        let sum = sawtooth().spread(1).add(Constant::<1>);
+        assert_eq!(sum.get_at(Index::new(1, 4)), 65);
+    }
+
     #[test]
     fn test_constant() {
         let mut osc = Constant::<42>;
@@ -574,7 +1105,7 @@ mod tests {
 
     #[test]
     fn test_sine() {
-        let mut sine = Sine { counter: 0 };
+        let mut sine = Sine::new();
 
         // At 0 degrees, sine should be 0
         assert_eq!(sine.get(), 0);
@@ -841,6 +1372,74 @@ mod tests {
         assert!(d != c); // Still producing different values
     }
 
+    #[test]
+    fn test_rng_normal() {
+        crate::rand::seed(42);
+        let rng = rng_normal(Constant::<40>, Constant::<32>);
+
+        let mut sum = 0i32;
+        for _ in 0..1000 {
+            let value = rng.get();
+            // Values stay within mean ± spread
+            assert!((8..=72).contains(&value), "{value} out of range");
+            sum += value as i32;
+        }
+
+        // Samples cluster around the mean
+        let mean = sum / 1000;
+        assert!((36..=44).contains(&mean), "mean was {mean}");
+    }
+
+    #[test]
+    fn test_rng_triangular() {
+        crate::rand::seed(42);
+        let rng = rng_triangular(Constant::<-40>, Constant::<16>);
+
+        for _ in 0..1000 {
+            let value = rng.get();
+            assert!((-56..=-24).contains(&value), "{value} out of range");
+        }
+    }
+
+    #[test]
+    fn test_rng_saturates() {
+        crate::rand::seed(42);
+        let rng = rng_normal(Constant::<120>, Constant::<127>);
+
+        // Values past the end of the i8 range clamp instead of wrapping
+        for _ in 0..1000 {
+            assert!(rng.get() >= -7);
+        }
+    }
+
+    #[test]
+    fn test_rng_weighted() {
+        crate::rand::seed(42);
+        let rng = rng_weighted([(-10, 1), (0, 0), (10, 3)]);
+
+        let mut counts = [0u32; 3];
+        for _ in 0..1000 {
+            match rng.get() {
+                -10 => counts[0] += 1,
+                0 => counts[1] += 1,
+                10 => counts[2] += 1,
+                value => panic!("unexpected value {value}"),
+            }
+        }
+
+        // Zero-weight entries are never picked
+        assert_eq!(counts[1], 0);
+        // Roughly a 1:3 split
+        assert!((200..=300).contains(&counts[0]), "{counts:?}");
+        assert!((700..=800).contains(&counts[2]), "{counts:?}");
+
+        // Large tables of heavy weights don't overflow the total
+        let mut table = [(0, 255); 300];
+        table[299] = (1, 255);
+        let rng = rng_weighted(table);
+        assert!((0..1000).any(|_| rng.get() == 1));
+    }
+
     #[test]
     fn test_random_pulse() {
         crate::rand::seed(123);
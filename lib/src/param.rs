@@ -0,0 +1,220 @@
+use crate::osc::{Oscillator, Seek, Value};
+use core::{cell::Cell, ops::Deref};
+
+/// Describes a named pattern parameter and the values it accepts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Spec {
+    pub name: &'static str,
+    pub min: Value,
+    pub max: Value,
+    pub default: Value,
+}
+
+impl Spec {
+    pub const fn new(name: &'static str, min: Value, max: Value, default: Value) -> Self {
+        assert!(min <= max, "parameter range is empty");
+        assert!(
+            default >= min && default <= max,
+            "parameter default is outside of its range"
+        );
+        Self {
+            name,
+            min,
+            max,
+            default,
+        }
+    }
+
+    /// A parameter that accepts the full oscillator range
+    pub const fn full(name: &'static str, default: Value) -> Self {
+        Self::new(name, Value::MIN, Value::MAX, default)
+    }
+
+    #[inline(always)]
+    pub fn clamp(&self, value: Value) -> Value {
+        value.clamp(self.min, self.max)
+    }
+}
+
+/// Parameters that can be listed and changed at runtime by name
+///
+/// Values are set through a shared reference, so a pattern can keep reading them while a UI or
+/// serial console changes them.
+pub trait Tunable {
+    fn specs(&self) -> &[Spec];
+    fn value_at(&self, index: u8) -> Value;
+
+    /// Sets a parameter by index, clamping it to its range and returning the applied value
+    fn set_at(&self, index: u8, value: Value) -> Value;
+
+    fn index_of(&self, name: &str) -> Option<u8> {
+        self.specs()
+            .iter()
+            .position(|spec| spec.name == name)
+            .map(|index| index as u8)
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        self.index_of(name).map(|index| self.value_at(index))
+    }
+
+    /// Sets a parameter by name, returning the applied value or `None` if there is no such parameter
+    fn set(&self, name: &str, value: Value) -> Option<Value> {
+        self.index_of(name).map(|index| self.set_at(index, value))
+    }
+
+    /// Restores every parameter to its default
+    fn reset(&self) {
+        for (index, spec) in self.specs().iter().enumerate() {
+            self.set_at(index as u8, spec.default);
+        }
+    }
+}
+
+/// A bank of parameter values described by a static schema
+pub struct Params<const N: usize> {
+    specs: &'static [Spec; N],
+    values: [Cell<Value>; N],
+}
+
+impl<const N: usize> Params<N> {
+    /// Creates the parameters with their default values
+    pub fn new(specs: &'static [Spec; N]) -> Self {
+        const {
+            if N > u8::MAX as usize {
+                panic!("Params supports at most 255 parameters");
+            }
+        }
+        Self {
+            specs,
+            values: core::array::from_fn(|index| Cell::new(specs[index].default)),
+        }
+    }
+}
+
+impl<const N: usize> Tunable for Params<N> {
+    #[inline(always)]
+    fn specs(&self) -> &[Spec] {
+        self.specs
+    }
+
+    #[inline(always)]
+    fn value_at(&self, index: u8) -> Value {
+        self.values[index as usize].get()
+    }
+
+    #[inline]
+    fn set_at(&self, index: u8, value: Value) -> Value {
+        let value = self.specs[index as usize].clamp(value);
+        self.values[index as usize].set(value);
+        value
+    }
+}
+
+/// An oscillator that reads the current value of a parameter
+///
+/// `P` is any handle to the parameters, such as `&Params<N>` or `Rc<Params<N>>`.
+#[derive(Clone)]
+pub struct Knob<P> {
+    params: P,
+    index: u8,
+}
+
+impl<P, const N: usize> Knob<P>
+where
+    P: Deref<Target = Params<N>>,
+{
+    /// Creates a knob for a named parameter
+    ///
+    /// Panics if there is no parameter with that name.
+    pub fn new(params: P, name: &str) -> Self {
+        let Some(index) = params.index_of(name) else {
+            panic!("no parameter named {name:?}");
+        };
+        Self { params, index }
+    }
+}
+
+impl<P, const N: usize> Oscillator for Knob<P>
+where
+    P: Deref<Target = Params<N>>,
+{
+    #[inline(always)]
+    fn tick(&mut self) {}
+
+    #[inline(always)]
+    fn get(&self) -> Value {
+        self.params.value_at(self.index)
+    }
+}
+
+/// Parameters only change when they are set, so a knob reads the same at any time
+impl<P, const N: usize> Seek for Knob<P>
+where
+    P: Deref<Target = Params<N>>,
+{
+    #[inline(always)]
+    fn value_at(&self, _t: u32) -> Value {
+        self.get()
+    }
+
+    #[inline(always)]
+    fn seek(&mut self, _t: u32) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    static SPECS: [Spec; 2] = [Spec::full("speed", 0), Spec::new("width", -10, 10, 5)];
+
+    #[test]
+    fn test_defaults() {
+        let params = Params::new(&SPECS);
+        assert_eq!(params.get("speed"), Some(0));
+        assert_eq!(params.get("width"), Some(5));
+        assert_eq!(params.get("missing"), None);
+    }
+
+    #[test]
+    fn test_set_clamps() {
+        let params = Params::new(&SPECS);
+        assert_eq!(params.set("width", 100), Some(10));
+        assert_eq!(params.get("width"), Some(10));
+        assert_eq!(params.set("width", -100), Some(-10));
+        assert_eq!(params.set("missing", 1), None);
+
+        params.reset();
+        assert_eq!(params.get("width"), Some(5));
+    }
+
+    #[test]
+    fn test_knob_follows_value() {
+        let params = Params::new(&SPECS);
+        let mut knob = Knob::new(&params, "speed");
+        assert_eq!(knob.get(), 0);
+
+        params.set("speed", 42);
+        knob.tick();
+        assert_eq!(knob.get(), 42);
+    }
+
+    #[test]
+    fn test_shared_knob() {
+        let params = Rc::new(Params::new(&SPECS));
+        let knob = Knob::new(params.clone(), "width");
+
+        let tunable: Rc<dyn Tunable> = params;
+        tunable.set("width", -3);
+        assert_eq!(knob.get(), -3);
+        assert_eq!(tunable.specs()[1].name, "width");
+    }
+
+    #[test]
+    #[should_panic(expected = "no parameter named \"missing\"")]
+    fn test_missing_knob() {
+        let params = Params::new(&SPECS);
+        Knob::new(&params, "missing");
+    }
+}
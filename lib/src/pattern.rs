@@ -1,4 +1,4 @@
-use crate::{color::Hsv, osc, storage::Storage};
+use crate::{color::Hsv, osc, star::Layout, storage::Storage, tip::Arrivals};
 
 #[derive(Clone, Copy)]
 pub struct Index {
@@ -6,6 +6,32 @@ pub struct Index {
     pub total: u8,
 }
 
+impl Index {
+    #[inline(always)]
+    pub const fn new(index: u8, total: u8) -> Self {
+        Self { index, total }
+    }
+
+    /// Position of the index within its total, normalized to 0..=255
+    ///
+    /// The first index maps to 0 and the last to 255.
+    #[inline(always)]
+    pub const fn fraction(self) -> u8 {
+        if self.total <= 1 {
+            return 0;
+        }
+        (self.index as u16 * 255 / (self.total - 1) as u16) as u8
+    }
+}
+
+/// The part of the star an LED belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Surface {
+    Spine,
+    Tip,
+    Arc,
+}
+
 pub trait Pattern {
     fn tick(&mut self);
     fn spine_color_at(&self, spine: Index, led: Index) -> Hsv;
@@ -13,6 +39,14 @@ pub trait Pattern {
     fn arc_color_at(&self, arc: Index, led: Index) -> Hsv;
 }
 
+/// Patterns whose state is a pure function of time, so they can jump straight to any frame
+///
+/// Patterns built only from [`osc::Seek`] oscillators and stateless combinators qualify.
+pub trait Seek: Pattern {
+    /// Moves the pattern to `t` ticks after it was created
+    fn seek(&mut self, t: u32);
+}
+
 impl Pattern for Hsv {
     #[inline(always)]
     fn tick(&mut self) {}
@@ -33,6 +67,11 @@ impl Pattern for Hsv {
     }
 }
 
+impl Seek for Hsv {
+    #[inline(always)]
+    fn seek(&mut self, _t: u32) {}
+}
+
 #[cfg(any(test, feature = "std"))]
 impl<T: ?Sized + Pattern> Pattern for Box<T> {
     #[inline(always)]
@@ -56,6 +95,14 @@ impl<T: ?Sized + Pattern> Pattern for Box<T> {
     }
 }
 
+#[cfg(any(test, feature = "std"))]
+impl<T: ?Sized + Seek> Seek for Box<T> {
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        (**self).seek(t);
+    }
+}
+
 pub struct Compound<Spine, Tip, Arc>
 where
     Spine: Pattern,
@@ -96,6 +143,32 @@ where
     }
 }
 
+impl<Spine, Tip, Arc> Seek for Compound<Spine, Tip, Arc>
+where
+    Spine: Seek,
+    Tip: Seek,
+    Arc: Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.spine.seek(t);
+        self.tip.seek(t);
+        self.arc.seek(t);
+    }
+}
+
+impl<Spine, Tip, Arc> Arrivals for Compound<Spine, Tip, Arc>
+where
+    Spine: Pattern + Arrivals,
+    Tip: Pattern,
+    Arc: Pattern,
+{
+    #[inline(always)]
+    fn arrived(&self, spine: Index) -> bool {
+        self.spine.arrived(spine)
+    }
+}
+
 pub struct PerSpine<V>
 where
     V: Storage,
@@ -142,6 +215,174 @@ where
     }
 }
 
+impl<V> Seek for PerSpine<V>
+where
+    V: Storage,
+    V::Value: Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        for v in self.values.iter_mut() {
+            v.seek(t);
+        }
+    }
+}
+
+impl<V> Arrivals for PerSpine<V>
+where
+    V: Storage,
+    V::Value: Pattern + Arrivals,
+{
+    #[inline(always)]
+    fn arrived(&self, spine: Index) -> bool {
+        self.values.get(spine.index).arrived(spine)
+    }
+}
+
+/// Routes each arc to its own pattern
+///
+/// Spines and tips are left dark.
+pub struct PerArc<V>
+where
+    V: Storage,
+    V::Value: Pattern,
+{
+    pub values: V,
+}
+
+impl<V> PerArc<V>
+where
+    V: Storage,
+    V::Value: Pattern,
+{
+    /// Creates a per-arc pattern, checking there is a value for every arc in the layout
+    ///
+    /// Arcs are numbered by the spine they leave, so there must be a value for every spine.
+    pub fn new(layout: &impl Layout, values: V) -> Self {
+        assert!(
+            values.len() >= layout.spines(),
+            "PerArc needs a pattern for the arcs after each of the {} spines, but only {} were provided",
+            layout.spines(),
+            values.len()
+        );
+        PerArc { values }
+    }
+}
+
+impl<V> Pattern for PerArc<V>
+where
+    V: Storage,
+    V::Value: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        for v in self.values.iter_mut() {
+            v.tick();
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, _spine: Index, _led: Index) -> Hsv {
+        Hsv::new(0, 0, 0)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, _spine: Index, _led: Index) -> Hsv {
+        Hsv::new(0, 0, 0)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.values.get(arc.index).arc_color_at(arc, led)
+    }
+}
+
+impl<V> Seek for PerArc<V>
+where
+    V: Storage,
+    V::Value: Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        for v in self.values.iter_mut() {
+            v.seek(t);
+        }
+    }
+}
+
+/// Routes each spine tip to its own pattern
+///
+/// Spines and arcs are left dark.
+pub struct PerTip<V>
+where
+    V: Storage,
+    V::Value: Pattern,
+{
+    pub values: V,
+}
+
+impl<V> PerTip<V>
+where
+    V: Storage,
+    V::Value: Pattern,
+{
+    /// Creates a per-tip pattern, checking there is a value for every spine in the layout
+    pub fn new(layout: &impl Layout, values: V) -> Self {
+        assert!(
+            values.len() >= layout.spines(),
+            "PerTip needs a pattern for each of the {} spine tips, but only {} were provided",
+            layout.spines(),
+            values.len()
+        );
+        PerTip { values }
+    }
+}
+
+impl<V> Pattern for PerTip<V>
+where
+    V: Storage,
+    V::Value: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        for v in self.values.iter_mut() {
+            v.tick();
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, _spine: Index, _led: Index) -> Hsv {
+        Hsv::new(0, 0, 0)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.values.get(spine.index).spine_tip_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, _arc: Index, _led: Index) -> Hsv {
+        Hsv::new(0, 0, 0)
+    }
+}
+
+impl<V> Seek for PerTip<V>
+where
+    V: Storage,
+    V::Value: Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        for v in self.values.iter_mut() {
+            v.seek(t);
+        }
+    }
+}
+
+/// A color made of three oscillators, one for each channel
+///
+/// Each channel is read at the spine or arc being drawn, so an [`osc::Spread`] gives every spine
+/// its own phase from a single shared oscillator.
 pub struct Oscillator<H, S, V> {
     pub h: H,
     pub s: S,
@@ -155,14 +396,10 @@ where
     V: osc::Oscillator,
 {
     #[inline(always)]
-    fn get(&self) -> Hsv {
-        let scale = |v: i8| -> u8 {
-            // Scale the oscillator signed to unsigned
-            (v as u8) + 128
-        };
-        let h = scale(self.h.get());
-        let s = scale(self.s.get());
-        let v = scale(self.v.get());
+    fn get(&self, index: Index) -> Hsv {
+        let h = osc::to_u8(self.h.get_at(index));
+        let s = osc::to_u8(self.s.get_at(index));
+        let v = osc::to_u8(self.v.get_at(index));
         Hsv::new(h, s, v)
     }
 }
@@ -181,17 +418,123 @@ where
     }
 
     #[inline(always)]
-    fn spine_color_at(&self, _spine: Index, _led: Index) -> Hsv {
-        self.get()
+    fn spine_color_at(&self, spine: Index, _led: Index) -> Hsv {
+        self.get(spine)
     }
 
     #[inline(always)]
-    fn spine_tip_color_at(&self, _spine: Index, _led: Index) -> Hsv {
-        self.get()
+    fn spine_tip_color_at(&self, spine: Index, _led: Index) -> Hsv {
+        self.get(spine)
     }
 
     #[inline(always)]
-    fn arc_color_at(&self, _arc: Index, _led: Index) -> Hsv {
-        self.get()
+    fn arc_color_at(&self, arc: Index, _led: Index) -> Hsv {
+        self.get(arc)
+    }
+}
+
+impl<H, S, V> Seek for Oscillator<H, S, V>
+where
+    H: osc::Seek,
+    S: osc::Seek,
+    V: osc::Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.h.seek(t);
+        self.s.seek(t);
+        self.v.seek(t);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::star::FixedLayout;
+
+    fn layout(spines: u8, arcs: u8) -> FixedLayout {
+        FixedLayout::new(spines, arcs, 3, 1, 2)
+    }
+
+    #[test]
+    fn test_seek() {
+        use crate::osc::{sawtooth, sine, triangle};
+
+        let pattern = || Compound {
+            spine: Oscillator {
+                h: sawtooth(),
+                s: triangle(),
+                v: sine(),
+            },
+            tip: Hsv::new(0, 0, 255),
+            arc: PerArc::new(
+                &layout(2, 2),
+                core::array::from_fn::<_, 2, _>(|_| Oscillator {
+                    h: triangle(),
+                    s: sine(),
+                    v: sawtooth(),
+                }),
+            ),
+        };
+        let spine = Index::new(1, 2);
+        let led = Index::new(0, 3);
+
+        let mut ticked = pattern();
+        for _ in 0..700 {
+            ticked.tick();
+        }
+        let mut sought = pattern();
+        sought.seek(2000);
+        sought.seek(700);
+
+        assert_eq!(
+            sought.spine_color_at(spine, led),
+            ticked.spine_color_at(spine, led)
+        );
+        assert_eq!(
+            sought.arc_color_at(spine, led),
+            ticked.arc_color_at(spine, led)
+        );
+    }
+
+    #[test]
+    fn test_per_arc() {
+        let values = [Hsv::new(1, 0, 0), Hsv::new(2, 0, 0), Hsv::new(3, 0, 0)];
+        let pattern = PerArc::new(&layout(2, 3), values);
+        let led = Index::new(0, 2);
+
+        assert_eq!(pattern.arc_color_at(Index::new(2, 3), led), values[2]);
+        assert_eq!(pattern.spine_color_at(Index::new(1, 2), led).v, 0);
+        assert_eq!(pattern.spine_tip_color_at(Index::new(1, 2), led).v, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "PerArc needs a pattern for the arcs after each of the 4 spines")]
+    fn test_per_arc_too_few() {
+        PerArc::new(&layout(4, 4), [Hsv::new(0, 0, 0); 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "PerArc needs a pattern for the arcs after each of the 3 spines")]
+    fn test_per_arc_fewer_arcs_than_spines() {
+        // Rendering still visits an arc after every spine
+        PerArc::new(&layout(3, 2), [Hsv::new(0, 0, 0); 2]);
+    }
+
+    #[test]
+    fn test_per_tip() {
+        let values = [Hsv::new(1, 0, 255), Hsv::new(2, 0, 255)];
+        let pattern = PerTip::new(&layout(2, 2), values);
+        let led = Index::new(0, 1);
+
+        assert_eq!(pattern.spine_tip_color_at(Index::new(1, 2), led), values[1]);
+        assert_eq!(pattern.spine_color_at(Index::new(1, 2), led).v, 0);
+        assert_eq!(pattern.arc_color_at(Index::new(1, 2), led).v, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "PerTip needs a pattern for each of the 3 spine tips")]
+    fn test_per_tip_too_few() {
+        PerTip::new(&layout(3, 3), [Hsv::new(0, 0, 0); 2]);
     }
 }
@@ -0,0 +1,475 @@
+use crate::{
+    color::Hsv,
+    pattern::{Index, Pattern, Surface},
+    storage::Storage,
+    transition::Style,
+};
+
+/// A fixed set of patterns addressed by index
+///
+/// Implemented for tuples of up to 8 patterns, which may all be different types, and for any
+/// [`Storage`] of a single pattern type.
+pub trait Patterns {
+    fn len(&self) -> u8;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn tick(&mut self, index: u8);
+    fn spine_color_at(&self, index: u8, spine: Index, led: Index) -> Hsv;
+    fn spine_tip_color_at(&self, index: u8, spine: Index, led: Index) -> Hsv;
+    fn arc_color_at(&self, index: u8, arc: Index, led: Index) -> Hsv;
+}
+
+impl<S> Patterns for S
+where
+    S: Storage,
+    S::Value: Pattern,
+{
+    #[inline(always)]
+    fn len(&self) -> u8 {
+        Storage::len(self)
+    }
+
+    #[inline(always)]
+    fn tick(&mut self, index: u8) {
+        self.get_mut(index).tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, index: u8, spine: Index, led: Index) -> Hsv {
+        self.get(index).spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, index: u8, spine: Index, led: Index) -> Hsv {
+        self.get(index).spine_tip_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, index: u8, arc: Index, led: Index) -> Hsv {
+        self.get(index).arc_color_at(arc, led)
+    }
+}
+
+macro_rules! impl_patterns_tuple {
+    ($len:literal; $($idx:tt $name:ident),*) => {
+        impl<$($name: Pattern),*> Patterns for ($($name,)*) {
+            #[inline(always)]
+            fn len(&self) -> u8 {
+                $len
+            }
+
+            #[inline(always)]
+            fn tick(&mut self, index: u8) {
+                match index {
+                    $($idx => self.$idx.tick(),)*
+                    _ => {}
+                }
+            }
+
+            #[inline(always)]
+            fn spine_color_at(&self, index: u8, spine: Index, led: Index) -> Hsv {
+                match index {
+                    $($idx => self.$idx.spine_color_at(spine, led),)*
+                    _ => Hsv::new(0, 0, 0),
+                }
+            }
+
+            #[inline(always)]
+            fn spine_tip_color_at(&self, index: u8, spine: Index, led: Index) -> Hsv {
+                match index {
+                    $($idx => self.$idx.spine_tip_color_at(spine, led),)*
+                    _ => Hsv::new(0, 0, 0),
+                }
+            }
+
+            #[inline(always)]
+            fn arc_color_at(&self, index: u8, arc: Index, led: Index) -> Hsv {
+                match index {
+                    $($idx => self.$idx.arc_color_at(arc, led),)*
+                    _ => Hsv::new(0, 0, 0),
+                }
+            }
+        }
+    };
+}
+
+impl_patterns_tuple!(1; 0 A);
+impl_patterns_tuple!(2; 0 A, 1 B);
+impl_patterns_tuple!(3; 0 A, 1 B, 2 C);
+impl_patterns_tuple!(4; 0 A, 1 B, 2 C, 3 D);
+impl_patterns_tuple!(5; 0 A, 1 B, 2 C, 3 D, 4 E);
+impl_patterns_tuple!(6; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+impl_patterns_tuple!(7; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+impl_patterns_tuple!(8; 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+
+/// Scheduling for a single pattern in a [`Playlist`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// Number of ticks the pattern plays for once it's fully shown
+    pub duration: u16,
+    /// How the pattern is brought in
+    pub transition: Style,
+    /// Number of ticks the transition into the pattern takes
+    pub transition_len: u16,
+    /// Relative likelihood of being picked with [`Order::Weighted`]
+    pub weight: u8,
+}
+
+impl Entry {
+    pub const fn new(duration: u16) -> Self {
+        Self {
+            duration,
+            transition: Style::Crossfade,
+            transition_len: 0,
+            weight: 1,
+        }
+    }
+
+    pub const fn with_transition(mut self, transition: Style, len: u16) -> Self {
+        self.transition = transition;
+        self.transition_len = len;
+        self
+    }
+
+    pub const fn with_weight(mut self, weight: u8) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// The order a [`Playlist`] moves through its patterns
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Order {
+    /// Play each pattern in turn, wrapping back to the first
+    #[default]
+    Sequential,
+    /// Play every pattern once in a random order before any repeats
+    Shuffle,
+    /// Pick each pattern at random in proportion to its weight
+    ///
+    /// The same pattern can be picked again, in which case it keeps playing without a transition.
+    Weighted,
+}
+
+/// Marks that no transition is in progress
+const NONE: u8 = u8::MAX;
+
+/// Cycles through a fixed set of patterns, transitioning between them
+///
+/// Only the playing pattern is ticked, plus the incoming one while a transition runs. All of the
+/// patterns and their [`Entry`] schedules live inline, so the playlist never allocates.
+pub struct Playlist<P, E>
+where
+    P: Patterns,
+    E: Storage<Value = Entry>,
+{
+    pub patterns: P,
+    pub entries: E,
+    pub order: Order,
+    current: u8,
+    next: u8,
+    elapsed: u16,
+    /// Bitset of the patterns played in the current shuffle round
+    played: u32,
+}
+
+impl<P, E> Playlist<P, E>
+where
+    P: Patterns,
+    E: Storage<Value = Entry>,
+{
+    pub fn new(patterns: P, entries: E, order: Order) -> Self {
+        assert_eq!(
+            patterns.len(),
+            entries.len(),
+            "Playlist needs exactly one entry per pattern"
+        );
+        assert!(!patterns.is_empty(), "Playlist needs at least one pattern");
+        assert!(
+            patterns.len() <= 32,
+            "Playlist supports at most 32 patterns"
+        );
+
+        Self {
+            patterns,
+            entries,
+            order,
+            current: 0,
+            next: NONE,
+            elapsed: 0,
+            played: 1,
+        }
+    }
+
+    /// Index of the pattern currently playing
+    ///
+    /// While a transition runs this is the outgoing pattern.
+    #[inline(always)]
+    pub fn current(&self) -> u8 {
+        self.current
+    }
+
+    /// Index of the incoming pattern, if a transition is in progress
+    #[inline(always)]
+    pub fn next(&self) -> Option<u8> {
+        if self.next == NONE {
+            None
+        } else {
+            Some(self.next)
+        }
+    }
+
+    fn choose_next(&mut self) -> u8 {
+        let len = self.patterns.len();
+        match self.order {
+            Order::Sequential => (self.current + 1) % len,
+            Order::Shuffle => {
+                let all = if len == 32 {
+                    u32::MAX
+                } else {
+                    (1u32 << len) - 1
+                };
+                if self.played == all {
+                    self.played = 0;
+                }
+
+                // Never repeat the pattern that just played, even across rounds
+                let remaining = all & !self.played & !(1 << self.current);
+                if remaining == 0 {
+                    return self.current;
+                }
+
+                let mut pick = crate::rand::below_u16(remaining.count_ones() as u16);
+                for index in 0..len {
+                    if remaining & (1 << index) == 0 {
+                        continue;
+                    }
+                    if pick == 0 {
+                        self.played |= 1 << index;
+                        return index;
+                    }
+                    pick -= 1;
+                }
+                self.current
+            }
+            Order::Weighted => {
+                let total: u16 = self.entries.iter().map(|e| e.weight as u16).sum();
+                let mut pick = crate::rand::below_u16(total);
+                for (index, entry) in self.entries.iter().enumerate() {
+                    let weight = entry.weight as u16;
+                    if pick < weight {
+                        return index as u8;
+                    }
+                    pick -= weight;
+                }
+                self.current
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn progress(&self) -> u8 {
+        let len = self.entries.get(self.next).transition_len;
+        (self.elapsed as u32 * 255 / len as u32) as u8
+    }
+
+    #[inline(always)]
+    fn mix(&self, surface: Surface, spine: Index, led: Index, from: Hsv, to: Hsv) -> Hsv {
+        let style = self.entries.get(self.next).transition;
+        style.mix(self.progress(), surface, spine, led, from, to)
+    }
+
+    #[inline(always)]
+    fn start(&mut self, next: u8) {
+        self.elapsed = 0;
+        if next == self.current || self.entries.get(next).transition_len == 0 {
+            self.current = next;
+        } else {
+            self.next = next;
+        }
+    }
+}
+
+impl<P, E> Pattern for Playlist<P, E>
+where
+    P: Patterns,
+    E: Storage<Value = Entry>,
+{
+    fn tick(&mut self) {
+        self.patterns.tick(self.current);
+
+        if self.next != NONE {
+            self.patterns.tick(self.next);
+            self.elapsed += 1;
+            if self.elapsed >= self.entries.get(self.next).transition_len {
+                self.current = self.next;
+                self.next = NONE;
+                self.elapsed = 0;
+            }
+            return;
+        }
+
+        self.elapsed = self.elapsed.saturating_add(1);
+        if self.elapsed >= self.entries.get(self.current).duration {
+            let next = self.choose_next();
+            self.start(next);
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let from = self.patterns.spine_color_at(self.current, spine, led);
+        if self.next == NONE {
+            return from;
+        }
+        let to = self.patterns.spine_color_at(self.next, spine, led);
+        self.mix(Surface::Spine, spine, led, from, to)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let from = self.patterns.spine_tip_color_at(self.current, spine, led);
+        if self.next == NONE {
+            return from;
+        }
+        let to = self.patterns.spine_tip_color_at(self.next, spine, led);
+        self.mix(Surface::Tip, spine, led, from, to)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let from = self.patterns.arc_color_at(self.current, arc, led);
+        if self.next == NONE {
+            return from;
+        }
+        let to = self.patterns.arc_color_at(self.next, arc, led);
+        self.mix(Surface::Arc, arc, led, from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{osc::Constant, pattern::Oscillator};
+
+    const SPINE: Index = Index::new(0, 1);
+    const LED: Index = Index::new(0, 1);
+
+    fn hue<P: Pattern>(pattern: &P) -> u8 {
+        pattern.spine_color_at(SPINE, LED).h
+    }
+
+    #[test]
+    fn test_sequential() {
+        let patterns = (
+            Hsv::new(0, 0, 0),
+            Hsv::new(1, 0, 0),
+            Oscillator {
+                h: Constant::<-126>,
+                s: Constant::<0>,
+                v: Constant::<0>,
+            },
+        );
+        let entries = [Entry::new(2), Entry::new(3), Entry::new(1)];
+        let mut playlist = Playlist::new(patterns, entries, Order::Sequential);
+
+        let mut hues = vec![];
+        for _ in 0..12 {
+            hues.push(hue(&playlist));
+            playlist.tick();
+        }
+
+        assert_eq!(hues, [0, 0, 1, 1, 1, 2, 0, 0, 1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_transition() {
+        let patterns = [Hsv::new(0, 0, 0), Hsv::new(0, 0, 200)];
+        let entries = [
+            Entry::new(1),
+            Entry::new(10).with_transition(Style::Crossfade, 4),
+        ];
+        let mut playlist = Playlist::new(patterns, entries, Order::Sequential);
+
+        playlist.tick();
+        assert_eq!(playlist.next(), Some(1));
+
+        let mut values = vec![];
+        for _ in 0..5 {
+            values.push(playlist.spine_color_at(SPINE, LED).v);
+            playlist.tick();
+        }
+
+        assert_eq!(values, [0, 49, 99, 149, 200]);
+        assert_eq!(playlist.current(), 1);
+        assert_eq!(playlist.next(), None);
+    }
+
+    #[test]
+    fn test_shuffle_plays_all_before_repeating() {
+        crate::rand::seed(3);
+        let patterns: [Hsv; 6] = core::array::from_fn(|i| Hsv::new(i as u8, 0, 0));
+        let entries = [Entry::new(1); 6];
+        let mut playlist = Playlist::new(patterns, entries, Order::Shuffle);
+
+        for _round in 0..4 {
+            let mut seen = 0u8;
+            for _ in 0..6 {
+                seen |= 1 << playlist.current();
+                playlist.tick();
+            }
+            assert_eq!(seen, 0b111111);
+        }
+    }
+
+    #[test]
+    fn test_weighted() {
+        crate::rand::seed(11);
+        let patterns = [Hsv::new(0, 0, 0), Hsv::new(1, 0, 0), Hsv::new(2, 0, 0)];
+        let entries = [
+            Entry::new(1).with_weight(1),
+            Entry::new(1).with_weight(0),
+            Entry::new(1).with_weight(3),
+        ];
+        let mut playlist = Playlist::new(patterns, entries, Order::Weighted);
+
+        let mut counts = [0u32; 3];
+        for _ in 0..1000 {
+            playlist.tick();
+            counts[playlist.current() as usize] += 1;
+        }
+
+        assert_eq!(counts[1], 0);
+        assert!(counts[2] > counts[0] * 2, "{counts:?}");
+    }
+
+    #[test]
+    fn test_only_ticks_active_patterns() {
+        use crate::osc::{Oscillator as _, sawtooth};
+
+        let osc = || Oscillator {
+            h: sawtooth(),
+            s: Constant::<0>,
+            v: Constant::<0>,
+        };
+        let entries = [Entry::new(5), Entry::new(5)];
+        let mut playlist = Playlist::new([osc(), osc()], entries, Order::Sequential);
+
+        for _ in 0..5 {
+            playlist.tick();
+        }
+
+        assert_eq!(playlist.current(), 1);
+        assert_eq!(playlist.patterns[0].h.get(), 5);
+        assert_eq!(playlist.patterns[1].h.get(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_entries() {
+        Playlist::new([Hsv::new(0, 0, 0)], [Entry::new(1); 2], Order::Sequential);
+    }
+}
@@ -52,6 +52,42 @@ pub fn i8() -> i8 {
     (val >> 8) as i8
 }
 
+/// Generate random u8
+pub fn u8() -> u8 {
+    (next() >> 8) as u8
+}
+
+/// Generate random u16 in range [0, bound)
+///
+/// Returns 0 if `bound` is 0
+pub fn below_u16(bound: u16) -> u16 {
+    // Multiply-shift keeps the high (better distributed) bits of the LCG
+    ((next() as u32 * bound as u32) >> 16) as u16
+}
+
+/// Generate random u32 in range [0, bound)
+///
+/// Returns 0 if `bound` is 0
+///
+/// Only 65536 distinct values can come out, spread evenly across the range.
+pub fn below_u32(bound: u32) -> u32 {
+    ((next() as u64 * bound as u64) >> 16) as u32
+}
+
+/// Generate a triangularly distributed i8 centered on 0
+///
+/// Sums two uniform samples so values near 0 are twice as likely as values near the extremes
+pub fn triangular_i8() -> i8 {
+    (i8() >> 1) + (i8() >> 1)
+}
+
+/// Generate an approximately normally distributed i8 centered on 0
+///
+/// Sums four uniform samples (Irwin-Hall), giving a standard deviation of ~37
+pub fn normal_i8() -> i8 {
+    (i8() >> 2) + (i8() >> 2) + (i8() >> 2) + (i8() >> 2)
+}
+
 /// Generate random u8 in range [min, max] inclusive
 pub fn range_u8(min: u8, max: u8) -> u8 {
     if min >= max {
@@ -89,6 +125,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_below_u16() {
+        seed(7);
+        for _ in 0..100 {
+            assert!(below_u16(300) < 300);
+        }
+        assert_eq!(below_u16(0), 0);
+    }
+
+    #[test]
+    fn test_normal_i8_clusters() {
+        seed(99);
+        let mut near = 0;
+        for _ in 0..1000 {
+            if normal_i8().unsigned_abs() < 32 {
+                near += 1;
+            }
+        }
+        // Uniform would land ~250 samples here, a normal with σ≈37 lands ~600
+        assert!(near > 500, "only {near} samples near the mean");
+    }
+
+    #[test]
+    fn test_triangular_i8_clusters() {
+        seed(99);
+        let mut near = 0;
+        for _ in 0..1000 {
+            if triangular_i8().unsigned_abs() < 32 {
+                near += 1;
+            }
+        }
+        // Uniform would land ~250 samples here, a triangle lands ~440
+        assert!(near > 350, "only {near} samples near the mean");
+    }
+
     #[test]
     fn test_range_u8_single() {
         let val = range_u8(5, 5);
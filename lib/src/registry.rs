@@ -0,0 +1,303 @@
+use crate::{
+    clock::{Counter, Time},
+    color::Hsv,
+    config,
+    param::{Params, Spec, Tunable},
+    pattern::{Index, Pattern, Seek},
+    star::FixedLayout,
+};
+use std::rc::Rc;
+
+/// Patterns that may or may not support seeking, so callers can check at runtime
+pub trait MaybeSeek: Pattern {
+    /// Whether [`MaybeSeek::seek_to`] moves the pattern
+    fn seekable(&self) -> bool;
+
+    /// Moves the pattern to `t` ticks after the start, if it supports seeking
+    fn seek_to(&mut self, t: u32);
+}
+
+impl<T: ?Sized + MaybeSeek> MaybeSeek for Box<T> {
+    #[inline(always)]
+    fn seekable(&self) -> bool {
+        (**self).seekable()
+    }
+
+    #[inline(always)]
+    fn seek_to(&mut self, t: u32) {
+        (**self).seek_to(t);
+    }
+}
+
+/// A pattern along with the way to seek it, if it has one
+struct Handle<P> {
+    pattern: P,
+    seek: Option<fn(&mut P, u32)>,
+}
+
+impl<P: Pattern> Pattern for Handle<P> {
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.pattern.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.pattern.spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.pattern.spine_tip_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.pattern.arc_color_at(arc, led)
+    }
+}
+
+impl<P: Pattern> MaybeSeek for Handle<P> {
+    #[inline(always)]
+    fn seekable(&self) -> bool {
+        self.seek.is_some()
+    }
+
+    #[inline(always)]
+    fn seek_to(&mut self, t: u32) {
+        if let Some(seek) = self.seek {
+            seek(&mut self.pattern, t);
+        }
+    }
+}
+
+/// A running pattern along with the layout it was built for and the parameters that tune it
+pub struct Instance {
+    pub layout: FixedLayout,
+    pub pattern: Box<dyn MaybeSeek>,
+    pub params: Rc<dyn Tunable>,
+}
+
+/// A built-in pattern that can be created by name at runtime
+pub struct Entry {
+    pub name: &'static str,
+    pub description: &'static str,
+    constructor: fn() -> Instance,
+}
+
+impl Entry {
+    /// Creates a fresh instance of the pattern with default parameters
+    pub fn create(&self) -> Instance {
+        (self.constructor)()
+    }
+}
+
+static NO_PARAMS: [Spec; 0] = [];
+
+fn fixed(pattern: impl Pattern + 'static) -> Instance {
+    Instance {
+        layout: config::fixed_layout(),
+        pattern: Box::new(Handle {
+            pattern,
+            seek: None,
+        }),
+        params: Rc::new(Params::new(&NO_PARAMS)),
+    }
+}
+
+fn seekable(pattern: impl Seek + 'static) -> Instance {
+    Instance {
+        layout: config::fixed_layout(),
+        pattern: Box::new(Handle {
+            pattern,
+            seek: Some(Seek::seek),
+        }),
+        params: Rc::new(Params::new(&NO_PARAMS)),
+    }
+}
+
+fn tuned<const N: usize, P>(specs: &'static [Spec; N], build: fn(Rc<Params<N>>) -> P) -> Instance
+where
+    P: Pattern + 'static,
+{
+    let params = Rc::new(Params::new(specs));
+    Instance {
+        layout: config::fixed_layout(),
+        pattern: Box::new(Handle {
+            pattern: build(params.clone()),
+            seek: None,
+        }),
+        params,
+    }
+}
+
+/// Every built-in pattern, built for the default [`config::Layout`] unless it needs another
+///
+/// The first entry is the default pattern.
+pub static PATTERNS: &[Entry] = &[
+    Entry {
+        name: "Classic",
+        description: "Random streaks along the spines with a streak circling the arcs",
+        constructor: || fixed(config::pattern()),
+    },
+    Entry {
+        name: "Tips",
+        description: "Classic streaks that flash the tips as they arrive, on a star with tips",
+        constructor: || Instance {
+            layout: config::tip_layout(),
+            ..fixed(config::tips_pattern())
+        },
+    },
+    Entry {
+        name: "Twinkle",
+        description: "Sparkles lighting up and fading all over the star",
+        constructor: || tuned(&config::TWINKLE_PARAMS, config::twinkle_pattern),
+    },
+    Entry {
+        name: "Fire",
+        description: "A flame rising along every spine",
+        constructor: || tuned(&config::FIRE_PARAMS, config::fire_pattern),
+    },
+    Entry {
+        name: "Ripple",
+        description: "Rings pulsing out from the center into the arcs",
+        constructor: || tuned(&config::RIPPLE_PARAMS, config::ripple_pattern),
+    },
+    Entry {
+        name: "Rainbow",
+        description: "A rainbow turning around the star",
+        constructor: || seekable(config::rainbow_pattern()),
+    },
+    Entry {
+        name: "Automaton",
+        description: "A cellular automaton that never settles",
+        constructor: || tuned(&config::AUTOMATON_PARAMS, config::automaton_pattern),
+    },
+    Entry {
+        name: "Orbit",
+        description: "A streak travelling along each spine in turn",
+        constructor: || tuned(&config::ORBIT_PARAMS, config::orbit_pattern),
+    },
+    Entry {
+        name: "Lightning",
+        description: "Bolts striking out from the center and branching into the arcs",
+        constructor: || tuned(&config::LIGHTNING_PARAMS, config::lightning_pattern),
+    },
+    Entry {
+        name: "Clock",
+        description: "A clock face counting up from ten past ten",
+        constructor: || {
+            // Counts frames at the visualizer's default speed of 30 ticks per second
+            let start = Time::new(10, 10, 0);
+            fixed(config::clock_pattern(Counter::new(start, 30)))
+        },
+    },
+];
+
+/// Names of every built-in pattern
+pub fn names() -> impl Iterator<Item = &'static str> {
+    PATTERNS.iter().map(|entry| entry.name)
+}
+
+/// Looks up a pattern by name, ignoring case
+pub fn find(name: &str) -> Option<&'static Entry> {
+    PATTERNS
+        .iter()
+        .find(|entry| entry.name.eq_ignore_ascii_case(name))
+}
+
+/// Creates a pattern by name, ignoring case
+pub fn create(name: &str) -> Option<Instance> {
+    find(name).map(Entry::create)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::star::{Layout as _, Star};
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut names: Vec<_> = names().map(str::to_lowercase).collect();
+        let len = names.len();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), len);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("fire").unwrap().name, "Fire");
+        assert_eq!(find("CLASSIC").unwrap().name, "Classic");
+        assert!(find("missing").is_none());
+        assert!(create("missing").is_none());
+    }
+
+    #[test]
+    fn test_params() {
+        let instance = create("ripple").unwrap();
+        let names: Vec<_> = instance
+            .params
+            .specs()
+            .iter()
+            .map(|spec| spec.name)
+            .collect();
+        assert_eq!(names, ["speed", "width", "decay"]);
+        assert_eq!(instance.params.set("speed", 10), Some(10));
+
+        assert!(create("classic").unwrap().params.specs().is_empty());
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut rainbow = create("rainbow").unwrap().pattern;
+        assert!(rainbow.seekable());
+        let mut ticked = create("rainbow").unwrap().pattern;
+        for _ in 0..5000 {
+            ticked.tick();
+        }
+        rainbow.seek_to(5000);
+        let led = Index::new(3, 12);
+        assert_eq!(
+            rainbow.spine_color_at(led, led),
+            ticked.spine_color_at(led, led)
+        );
+
+        assert!(!create("fire").unwrap().pattern.seekable());
+    }
+
+    #[test]
+    fn test_every_pattern_renders() {
+        for entry in PATTERNS {
+            let instance = entry.create();
+            let mut star = Star::new(instance.layout, instance.pattern);
+            let mut lit = false;
+            for _ in 0..256 {
+                star.tick();
+                assert_eq!(star.iter().len(), star.layout.leds() as usize);
+                lit |= star.iter().any(|color| color.v > 0);
+            }
+            assert!(lit, "{} never lit any LEDs", entry.name);
+        }
+    }
+
+    #[test]
+    fn test_tips() {
+        let instance = create("tips").unwrap();
+        assert_eq!(instance.layout.tip_len_at(0), 1);
+
+        let mut pattern = instance.pattern;
+        let tip = Index::new(0, 1);
+        let mut flashed = false;
+        for _ in 0..1024 {
+            pattern.tick();
+            for spine in 0..12 {
+                let color = pattern.spine_tip_color_at(Index::new(spine, 12), tip);
+                // Tips glow at ~1/8 brightness and only go brighter when flashing or sparkling
+                assert!(color.v >= 32, "{color:?}");
+                flashed |= color.v > 128;
+            }
+        }
+        assert!(flashed);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::{
+    color::{Hsv, scale8},
+    osc,
+    pattern::{Index, Pattern},
+    slotmap::SlotMap,
+    star::Layout,
+    storage::Storage,
+};
+
+/// A ring travelling out from the center, stored in 2 bytes
+///
+/// The position is the ring's distance from the center in 8.8 fixed-point LEDs.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ring {
+    position: u16,
+}
+
+impl Ring {
+    /// Distance from the center in whole LEDs
+    #[inline(always)]
+    pub fn position(&self) -> u8 {
+        (self.position >> 8) as u8
+    }
+}
+
+/// Rings of light expanding from the center along every spine at once
+///
+/// A new ring is emitted each time `trigger` rises above 0. Once a ring passes the tips it can
+/// carry on into the arcs, entering from both ends and meeting in the middle.
+///
+/// - `speed` sets how far rings travel each tick, from ~0 (-128) to 2 LEDs (127)
+/// - `width` sets the ring width, from 1 (-128) to 32 LEDs (127)
+/// - `decay` sets how much brightness a ring loses per LED travelled, from none (-128) to ~6%
+///   (127)
+pub struct Ripple<Trigger, Speed, Width, Decay, Inner, Rings>
+where
+    Trigger: osc::Oscillator,
+    Speed: osc::Oscillator,
+    Width: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+    Rings: Storage<Value = Ring>,
+{
+    pub trigger: Trigger,
+    pub speed: Speed,
+    pub width: Width,
+    pub decay: Decay,
+    pub inner: Inner,
+    pub into_arcs: bool,
+    pub rings: SlotMap<Ring, Rings, 8>,
+    spine_len: u8,
+    tip_len: u8,
+    arc_len: u8,
+    armed: bool,
+}
+
+impl<Trigger, Speed, Width, Decay, Inner, Rings> Ripple<Trigger, Speed, Width, Decay, Inner, Rings>
+where
+    Trigger: osc::Oscillator,
+    Speed: osc::Oscillator,
+    Width: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+    Rings: Storage<Value = Ring>,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        layout: &impl Layout,
+        trigger: Trigger,
+        speed: Speed,
+        width: Width,
+        decay: Decay,
+        into_arcs: bool,
+        inner: Inner,
+        rings: Rings,
+    ) -> Self {
+        Self {
+            trigger,
+            speed,
+            width,
+            decay,
+            inner,
+            into_arcs,
+            rings: SlotMap::new(rings),
+            spine_len: layout.spine_len_at(0),
+            tip_len: layout.tip_len_at(0),
+            arc_len: layout.arc_len_at(0),
+            armed: true,
+        }
+    }
+
+    #[inline(always)]
+    fn width_leds(&self) -> u8 {
+        (osc::to_u8(self.width.get()) >> 3) + 1
+    }
+
+    #[inline(always)]
+    fn decay_per_led(&self) -> u8 {
+        osc::to_u8(self.decay.get()) >> 4
+    }
+
+    /// How far rings travel before there is nothing left to light
+    #[inline(always)]
+    fn end(&self) -> u16 {
+        let mut end = self.spine_len as u16 + self.tip_len as u16;
+        if self.into_arcs {
+            end += self.arc_len.div_ceil(2) as u16;
+        }
+        end + self.width_leds() as u16
+    }
+
+    /// Brightness of the rings at a distance from the center
+    fn brightness(&self, distance: u16) -> u8 {
+        let width = (self.width_leds() as u32) << 8;
+        let decay = self.decay_per_led() as u16;
+        let distance = (distance as u32) << 8;
+
+        let mut brightness = 0u8;
+        for ring in self.rings.iter() {
+            let offset = (ring.position as u32).abs_diff(distance);
+            if offset >= width {
+                continue;
+            }
+            let shape = ((width - offset) * 255 / width) as u8;
+            let fade = 255u16.saturating_sub(decay * ring.position() as u16) as u8;
+            brightness = brightness.max(scale8(shape, fade));
+        }
+        brightness
+    }
+
+    #[inline(always)]
+    fn color(&self, mut color: Hsv, distance: u16) -> Hsv {
+        color.v = scale8(color.v, self.brightness(distance));
+        color
+    }
+}
+
+impl<Trigger, Speed, Width, Decay, Inner, Rings> Pattern
+    for Ripple<Trigger, Speed, Width, Decay, Inner, Rings>
+where
+    Trigger: osc::Oscillator,
+    Speed: osc::Oscillator,
+    Width: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+    Rings: Storage<Value = Ring>,
+{
+    fn tick(&mut self) {
+        self.trigger.tick();
+        self.speed.tick();
+        self.width.tick();
+        self.decay.tick();
+        self.inner.tick();
+
+        // Only emit on the rising edge so a held trigger makes a single ring
+        let triggered = self.trigger.get() > 0;
+        if triggered && self.armed {
+            self.rings.insert(Ring::default());
+        }
+        self.armed = !triggered;
+
+        let step = (osc::to_u8(self.speed.get()) as u16 + 1) << 1;
+        let end = self.end() << 8;
+        let decay = self.decay_per_led() as u16;
+        self.rings.retain(|ring| {
+            ring.position = ring.position.saturating_add(step);
+            let faded = decay * ring.position() as u16 >= 255;
+            ring.position < end && !faded
+        });
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.color(self.inner.spine_color_at(spine, led), led.index as u16)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let distance = self.spine_len as u16 + led.index as u16;
+        self.color(self.inner.spine_tip_color_at(spine, led), distance)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let color = self.inner.arc_color_at(arc, led);
+        if !self.into_arcs {
+            return Hsv { v: 0, ..color };
+        }
+        // Rings enter from both ends of the arc at once
+        let from_end = led.index.min(led.total - 1 - led.index);
+        let distance = self.spine_len as u16 + self.tip_len as u16 + from_end as u16;
+        self.color(color, distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        osc::{Constant, OscillatorExt as _, sawtooth},
+        star::FixedLayout,
+    };
+
+    const WHITE: Hsv = Hsv::new(0, 0, 255);
+    const SPINE: Index = Index::new(0, 12);
+
+    fn spine_values(pattern: &impl Pattern, spine: Index) -> Vec<u8> {
+        (0..10)
+            .map(|led| pattern.spine_color_at(spine, Index::new(led, 10)).v)
+            .collect()
+    }
+
+    #[test]
+    fn test_ring_moves_out() {
+        let mut ripple = Ripple::new(
+            &FixedLayout::new(12, 12, 10, 0, 5),
+            Constant::<1>,
+            Constant::<127>, // ~2 LEDs per tick
+            Constant::<{ i8::MIN }>,
+            Constant::<{ i8::MIN }>,
+            false,
+            WHITE,
+            [Ring::default(); 8],
+        );
+
+        ripple.tick();
+        let first = spine_values(&ripple, SPINE);
+        for _ in 0..2 {
+            ripple.tick();
+        }
+        let later = spine_values(&ripple, SPINE);
+
+        let peak = |values: &[u8]| {
+            let max = values.iter().max().unwrap();
+            values.iter().position(|v| v == max).unwrap()
+        };
+        assert!(peak(&later) > peak(&first), "{first:?} {later:?}");
+
+        // Every spine shows the same ring
+        assert_eq!(spine_values(&ripple, Index::new(7, 12)), later);
+
+        // Holding the trigger high only emits a single ring
+        assert_eq!(ripple.rings.len(), 1);
+    }
+
+    #[test]
+    fn test_continues_into_arcs() {
+        let arc = |ripple: &Ripple<_, _, _, _, _, _>, led| {
+            ripple.arc_color_at(Index::new(3, 12), Index::new(led, 5)).v
+        };
+
+        for into_arcs in [false, true] {
+            let mut ripple = Ripple::new(
+                &FixedLayout::new(12, 12, 10, 0, 5),
+                Constant::<1>,
+                Constant::<127>,
+                Constant::<{ i8::MIN }>,
+                Constant::<{ i8::MIN }>,
+                into_arcs,
+                WHITE,
+                [Ring::default(); 8],
+            );
+            // Travel past the tips
+            for _ in 0..5 {
+                ripple.tick();
+            }
+            assert_eq!(arc(&ripple, 0) > 0, into_arcs);
+            assert_eq!(arc(&ripple, 0), arc(&ripple, 4));
+        }
+    }
+
+    #[test]
+    fn test_decay_and_expiry() {
+        let mut ripple = Ripple::new(
+            &FixedLayout::new(12, 12, 10, 0, 5),
+            sawtooth().saturating_sub(100),
+            Constant::<0>,
+            Constant::<0>,
+            Constant::<127>,
+            true,
+            WHITE,
+            [Ring::default(); 8],
+        );
+
+        let mut max_rings = 0u8;
+        for _ in 0..512 {
+            ripple.tick();
+            max_rings = max_rings.max(ripple.rings.len());
+        }
+        // Rings are emitted once per cycle of the trigger and fade out well before the next
+        assert_eq!(max_rings, 1);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     color::Hsv,
-    pattern::{Index, Pattern},
+    pattern::{Index, Pattern, Surface},
 };
 
 pub trait Layout {
@@ -24,6 +24,20 @@ pub struct FixedLayout {
 }
 
 impl FixedLayout {
+    /// Creates a layout where every spine, tip and arc has the same length
+    pub fn new(spines: u8, arcs: u8, spine_len: u8, tip_len: u8, arc_len: u8) -> Self {
+        let mut layout = Self {
+            spines,
+            arcs,
+            leds: 0,
+            spine_len,
+            tip_len,
+            arc_len,
+        };
+        layout.update_led_count();
+        layout
+    }
+
     pub fn update_led_count(&mut self) {
         let spines = self.spines as u16 * self.spine_len as u16 * 2;
         let arcs = self.arcs as u16 * self.arc_len as u16;
@@ -64,6 +78,80 @@ impl Layout for FixedLayout {
     }
 }
 
+/// Dense numbering for every logical LED on the star
+///
+/// Spine LEDs come first, then arcs, then tips. The outgoing and returning halves of a spine show
+/// the same colors, so they share a cell. Every spine, arc and tip is assumed to be the same
+/// length as the first one in the layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cells {
+    arc_offset: u16,
+    tip_offset: u16,
+    len: u16,
+}
+
+impl Cells {
+    pub fn new(layout: &impl Layout) -> Self {
+        let spines = layout.spines() as u16;
+        let arc_offset = spines * layout.spine_len_at(0) as u16;
+        let tip_offset = arc_offset + layout.arcs() as u16 * layout.arc_len_at(0) as u16;
+        let len = tip_offset + spines * layout.tip_len_at(0) as u16;
+        Self {
+            arc_offset,
+            tip_offset,
+            len,
+        }
+    }
+
+    /// Total number of cells
+    #[inline(always)]
+    pub fn len(&self) -> u16 {
+        self.len
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    pub fn spine(&self, spine: Index, led: Index) -> u16 {
+        spine.index as u16 * led.total as u16 + led.index as u16
+    }
+
+    #[inline(always)]
+    pub fn arc(&self, arc: Index, led: Index) -> u16 {
+        self.arc_offset + arc.index as u16 * led.total as u16 + led.index as u16
+    }
+
+    #[inline(always)]
+    pub fn tip(&self, spine: Index, led: Index) -> u16 {
+        self.tip_offset + spine.index as u16 * led.total as u16 + led.index as u16
+    }
+
+    /// Cell of an LED on any surface
+    #[inline(always)]
+    pub fn at(&self, surface: Surface, strip: Index, led: Index) -> u16 {
+        match surface {
+            Surface::Spine => self.spine(strip, led),
+            Surface::Arc => self.arc(strip, led),
+            Surface::Tip => self.tip(strip, led),
+        }
+    }
+
+    /// The surface a cell lies on, along with its position among that surface's cells
+    #[inline(always)]
+    pub fn locate(&self, cell: u16) -> (Surface, u16) {
+        if cell < self.arc_offset {
+            (Surface::Spine, cell)
+        } else if cell < self.tip_offset {
+            (Surface::Arc, cell - self.arc_offset)
+        } else {
+            (Surface::Tip, cell - self.tip_offset)
+        }
+    }
+}
+
 /// 3D star configuration with compile-time spine count and arc LED count
 ///
 /// The star consists of:
@@ -125,7 +213,7 @@ enum Position {
     Arc,
 }
 
-impl<'a, L, P> StarIter<'a, L, P>
+impl<L, P> StarIter<'_, L, P>
 where
     L: Layout,
     P: Pattern,
@@ -195,7 +283,7 @@ where
     }
 }
 
-impl<'a, L, P> Iterator for StarIter<'a, L, P>
+impl<L, P> Iterator for StarIter<'_, L, P>
 where
     L: Layout,
     P: Pattern,
@@ -275,7 +363,7 @@ where
     }
 }
 
-impl<'a, L, P> ExactSizeIterator for StarIter<'a, L, P>
+impl<L, P> ExactSizeIterator for StarIter<'_, L, P>
 where
     L: Layout,
     P: Pattern,
@@ -578,6 +666,30 @@ mod tests {
         assert!(colors.iter().all(|c| c.v == 255));
     }
 
+    #[test]
+    fn test_cells_cover_every_led_once() {
+        let layout = FixedLayout::new(3, 3, 4, 1, 2);
+        let cells = Cells::new(&layout);
+        assert_eq!(cells.len(), 3 * 4 + 3 * 2 + 3);
+
+        let mut seen = vec![false; cells.len() as usize];
+        let mut mark = |cell: u16| {
+            assert!(!seen[cell as usize], "cell {cell} visited twice");
+            seen[cell as usize] = true;
+        };
+        for spine in 0..3 {
+            let spine = Index::new(spine, 3);
+            for led in 0..4 {
+                mark(cells.spine(spine, Index::new(led, 4)));
+            }
+            for led in 0..2 {
+                mark(cells.arc(spine, Index::new(led, 2)));
+            }
+            mark(cells.tip(spine, Index::new(0, 1)));
+        }
+        assert!(seen.iter().all(|v| *v));
+    }
+
     #[test]
     fn test_single_led_spine() {
         // Edge case: spine with just 1 LED
@@ -1,4 +1,12 @@
-use crate::{color::Hsv, osc, pattern::*, slotmap::SlotMap, storage::Storage};
+use crate::{
+    color::Hsv,
+    graph::{Node, Path, Segment},
+    osc,
+    pattern::*,
+    slotmap::SlotMap,
+    storage::Storage,
+    tip::Arrivals,
+};
 use core::fmt;
 
 /// A bitpacked streak state stored in 2 bytes
@@ -113,6 +121,7 @@ where
     pub total_leds: TotalLeds,
     pub inner: Inner,
     pub streaks: SlotMap<StreakState, Streaks, 8>,
+    arrived: bool,
 }
 
 impl<Spawner, Length, Velocity, TotalLeds, Inner, Streaks> fmt::Debug
@@ -137,6 +146,7 @@ where
             .field("total_leds", &self.total_leds)
             .field("inner", &self.inner)
             .field("streaks", &self.streaks)
+            .field("arrived", &self.arrived)
             .finish()
     }
 }
@@ -166,6 +176,7 @@ where
             total_leds,
             inner,
             streaks: SlotMap::new(streaks),
+            arrived: false,
         }
     }
 }
@@ -204,14 +215,18 @@ where
 
         // Update all active streaks
         let total_leds = self.total_leds.get() as u8;
+        let mut arrived = false;
         self.streaks.retain(|streak| {
+            let before = streak.position();
             streak.tick();
 
             // Keep alive while tail is visible
             let head_pos = streak.position();
+            arrived |= before < total_leds && head_pos >= total_leds;
             let tail_pos = head_pos.saturating_sub(streak.length());
             tail_pos < total_leds
         });
+        self.arrived = arrived;
     }
 
     #[inline(always)]
@@ -286,6 +301,23 @@ where
     }
 }
 
+impl<Spawner, Length, Velocity, TotalLeds, Inner, Streaks> Arrivals
+    for StreakSpawner<Spawner, Length, Velocity, TotalLeds, Inner, Streaks>
+where
+    Spawner: osc::Oscillator,
+    Length: osc::Oscillator,
+    Velocity: osc::Oscillator,
+    TotalLeds: osc::Oscillator,
+    Inner: Pattern,
+    Streaks: Storage<Value = StreakState>,
+{
+    /// Whether a streak's head ran past the last LED during the last tick
+    #[inline(always)]
+    fn arrived(&self, _spine: Index) -> bool {
+        self.arrived
+    }
+}
+
 /// Arc-specific streak that visits all spines in circular order
 /// Uses fixed-point position that wraps at total arc LEDs
 pub struct ArcStreak<Length, Velocity, Inner, const ARC_LEN: u8, const TOTAL_ARCS: u8>
@@ -410,6 +442,113 @@ where
     }
 }
 
+/// A streak that follows a [`Path`] across spines, tips and arcs
+///
+/// Unlike [`StreakSpawner`] and [`ArcStreak`], the streak moves continuously from one strip into
+/// the next, wrapping back to the start of the path when it reaches the end. LEDs off the path are
+/// left dark.
+pub struct PathStreak<Length, Velocity, Inner, Segments>
+where
+    Length: osc::Oscillator,
+    Velocity: osc::Oscillator,
+    Inner: Pattern,
+    Segments: Storage<Value = Segment>,
+{
+    position: u16, // 15.1 fixed-point position
+    pub length: Length,
+    pub velocity: Velocity,
+    pub inner: Inner,
+    pub path: Path<Segments>,
+}
+
+impl<Length, Velocity, Inner, Segments> PathStreak<Length, Velocity, Inner, Segments>
+where
+    Length: osc::Oscillator,
+    Velocity: osc::Oscillator,
+    Inner: Pattern,
+    Segments: Storage<Value = Segment>,
+{
+    pub fn new(length: Length, velocity: Velocity, inner: Inner, path: Path<Segments>) -> Self {
+        Self {
+            position: 0,
+            length,
+            velocity,
+            inner,
+            path,
+        }
+    }
+
+    /// Position of the streak head along the path
+    #[inline(always)]
+    pub fn head(&self) -> u16 {
+        self.position >> 1
+    }
+
+    fn calculate_color(&self, mut color: Hsv, node: Node) -> Hsv {
+        let length = map_i8_to_5bit(self.length.get());
+        let len = self.path.len();
+        let head = self.head();
+
+        let mut brightness = 0u8;
+        if length > 0 {
+            for position in self.path.positions(node) {
+                // Distance behind the head, wrapping around the end of the path
+                let distance = if head >= position {
+                    head - position
+                } else {
+                    len - position + head
+                };
+                if distance > length as u16 {
+                    continue;
+                }
+                let remaining = length - distance as u8;
+                let factor = ((remaining as u16 * 255) / length as u16) as u8;
+                brightness = brightness.max(factor);
+            }
+        }
+
+        color.v = brightness;
+        color
+    }
+}
+
+impl<Length, Velocity, Inner, Segments> Pattern for PathStreak<Length, Velocity, Inner, Segments>
+where
+    Length: osc::Oscillator,
+    Velocity: osc::Oscillator,
+    Inner: Pattern,
+    Segments: Storage<Value = Segment>,
+{
+    fn tick(&mut self) {
+        self.length.tick();
+        self.velocity.tick();
+        self.inner.tick();
+
+        if self.path.is_empty() {
+            return;
+        }
+
+        let velocity = map_i8_to_3bit(self.velocity.get());
+        let vel_mult = velocity_to_multiplier(velocity) as u16;
+        self.position = (self.position + vel_mult) % (self.path.len() << 1);
+    }
+
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let node = Node::new(Surface::Spine, spine.index, led.index);
+        self.calculate_color(self.inner.spine_color_at(spine, led), node)
+    }
+
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let node = Node::new(Surface::Tip, spine.index, led.index);
+        self.calculate_color(self.inner.spine_tip_color_at(spine, led), node)
+    }
+
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let node = Node::new(Surface::Arc, arc.index, led.index);
+        self.calculate_color(self.inner.arc_color_at(arc, led), node)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -541,4 +680,46 @@ mod tests {
 
         assert_snapshot!(run(64, 16, pattern));
     }
+
+    #[test]
+    fn test_path_streak_crosses_strips() {
+        use crate::{graph::Graph, star::FixedLayout};
+
+        let layout = FixedLayout::new(4, 4, 5, 0, 3);
+        let path = Path::new(
+            Graph::new(&layout),
+            [Segment::spine_in(0), Segment::arc(0), Segment::spine_out(1)],
+        );
+        let mut pattern = PathStreak::new(
+            Constant::<-120>, // 1 LED long
+            Constant::<127>,  // 2 LEDs per tick
+            Hsv::new(0, 0, 255),
+            path,
+        );
+        let spine = |index| Index::new(index, 4);
+
+        // Starts at the outer end of spine 0
+        assert_eq!(pattern.spine_color_at(spine(0), Index::new(4, 5)).v, 255);
+
+        // Crosses into the arc
+        for _ in 0..3 {
+            pattern.tick();
+        }
+        assert_eq!(pattern.head(), 6);
+        assert_eq!(pattern.arc_color_at(spine(0), Index::new(1, 3)).v, 255);
+        assert_eq!(pattern.spine_color_at(spine(0), Index::new(0, 5)).v, 0);
+
+        // And out along the next spine
+        for _ in 0..2 {
+            pattern.tick();
+        }
+        assert_eq!(pattern.spine_color_at(spine(1), Index::new(2, 5)).v, 255);
+        assert_eq!(pattern.arc_color_at(spine(0), Index::new(1, 3)).v, 0);
+
+        // Wraps back to the start of the path
+        for _ in 0..2 {
+            pattern.tick();
+        }
+        assert_eq!(pattern.head(), 1);
+    }
 }
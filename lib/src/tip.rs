@@ -0,0 +1,479 @@
+use crate::{
+    color::Hsv,
+    layer::Blend,
+    osc,
+    pattern::{Index, Pattern},
+    star::Layout,
+    storage::Storage,
+};
+
+/// Patterns that can report something reaching the outer end of a spine
+pub trait Arrivals {
+    /// Whether something reached the end of the spine during the last tick
+    fn arrived(&self, spine: Index) -> bool;
+}
+
+/// Color the inner pattern shows on the last LED of a spine
+#[inline(always)]
+fn end_color(inner: &impl Pattern, spine: Index, spine_len: u8) -> Hsv {
+    inner.spine_color_at(spine, Index::new(spine_len.saturating_sub(1), spine_len))
+}
+
+/// Brightness lost per tick, from 1 (-128) to 32 (127)
+#[inline(always)]
+fn decay_step(decay: osc::Value) -> u8 {
+    (osc::to_u8(decay) >> 3) + 1
+}
+
+fn check_levels(name: &str, layout: &impl Layout, levels: &impl Storage<Value = u8>) {
+    assert!(
+        levels.len() >= layout.spines(),
+        "{name} needs a level for each of the {} spine tips, but only {} were provided",
+        layout.spines(),
+        levels.len()
+    );
+}
+
+/// Fills `lengths` with the length of every spine, so spines of different lengths each sample
+/// their own last LED
+fn spine_lens<Lengths>(name: &str, layout: &impl Layout, mut lengths: Lengths) -> Lengths
+where
+    Lengths: Storage<Value = u8>,
+{
+    assert!(
+        lengths.len() >= layout.spines(),
+        "{name} needs a length for each of the {} spines, but only {} were provided",
+        layout.spines(),
+        lengths.len()
+    );
+    for spine in 0..layout.spines() {
+        lengths.set(spine, layout.spine_len_at(spine));
+    }
+    lengths
+}
+
+/// Lights the tips with the hue and saturation at the end of each spine
+///
+/// - `level` sets the glow's brightness, from off (-128) to full (127)
+pub struct Glow<Level, Inner, Lengths>
+where
+    Level: osc::Oscillator,
+    Inner: Pattern,
+    Lengths: Storage<Value = u8>,
+{
+    pub level: Level,
+    pub inner: Inner,
+    lengths: Lengths,
+}
+
+impl<Level, Inner, Lengths> Glow<Level, Inner, Lengths>
+where
+    Level: osc::Oscillator,
+    Inner: Pattern,
+    Lengths: Storage<Value = u8>,
+{
+    /// `lengths` is scratch storage with a byte per spine, which is overwritten with the length of
+    /// each spine in `layout`
+    pub fn new(layout: &impl Layout, level: Level, inner: Inner, lengths: Lengths) -> Self {
+        Self {
+            level,
+            inner,
+            lengths: spine_lens("Glow", layout, lengths),
+        }
+    }
+}
+
+impl<Level, Inner, Lengths> Pattern for Glow<Level, Inner, Lengths>
+where
+    Level: osc::Oscillator,
+    Inner: Pattern,
+    Lengths: Storage<Value = u8>,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.level.tick();
+        self.inner.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, _led: Index) -> Hsv {
+        Hsv {
+            v: osc::to_u8(self.level.get()),
+            ..end_color(&self.inner, spine, *self.lengths.get(spine.index))
+        }
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.inner.arc_color_at(arc, led)
+    }
+}
+
+impl<Level, Inner, Lengths> Arrivals for Glow<Level, Inner, Lengths>
+where
+    Level: osc::Oscillator,
+    Inner: Pattern + Arrivals,
+    Lengths: Storage<Value = u8>,
+{
+    #[inline(always)]
+    fn arrived(&self, spine: Index) -> bool {
+        self.inner.arrived(spine)
+    }
+}
+
+/// Flashes a tip whenever something arrives at the end of its spine
+///
+/// The flash takes its color from the end of the spine and is lightened over the inner pattern's
+/// tip, so it can sit on top of a [`Glow`].
+///
+/// - `decay` sets how quickly a flash fades, from 1 (-128) to 32 (127) brightness per tick
+pub struct Flash<Decay, Inner, Levels, Lengths>
+where
+    Decay: osc::Oscillator,
+    Inner: Pattern + Arrivals,
+    Levels: Storage<Value = u8>,
+    Lengths: Storage<Value = u8>,
+{
+    pub decay: Decay,
+    pub inner: Inner,
+    levels: Levels,
+    lengths: Lengths,
+    spines: u8,
+}
+
+impl<Decay, Inner, Levels, Lengths> Flash<Decay, Inner, Levels, Lengths>
+where
+    Decay: osc::Oscillator,
+    Inner: Pattern + Arrivals,
+    Levels: Storage<Value = u8>,
+    Lengths: Storage<Value = u8>,
+{
+    /// `levels` holds the brightness of each tip's flash and `lengths` is overwritten with each
+    /// spine's length as in [`Glow::new`], so both need a byte per spine
+    pub fn new(
+        layout: &impl Layout,
+        decay: Decay,
+        inner: Inner,
+        levels: Levels,
+        lengths: Lengths,
+    ) -> Self {
+        check_levels("Flash", layout, &levels);
+        Self {
+            decay,
+            inner,
+            levels,
+            lengths: spine_lens("Flash", layout, lengths),
+            spines: layout.spines(),
+        }
+    }
+}
+
+impl<Decay, Inner, Levels, Lengths> Pattern for Flash<Decay, Inner, Levels, Lengths>
+where
+    Decay: osc::Oscillator,
+    Inner: Pattern + Arrivals,
+    Levels: Storage<Value = u8>,
+    Lengths: Storage<Value = u8>,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.decay.tick();
+        self.inner.tick();
+
+        let step = decay_step(self.decay.get());
+        for spine in 0..self.spines {
+            let level = self.levels.get_mut(spine);
+            *level = if self.inner.arrived(Index::new(spine, self.spines)) {
+                255
+            } else {
+                level.saturating_sub(step)
+            };
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let flash = Hsv {
+            v: *self.levels.get(spine.index),
+            ..end_color(&self.inner, spine, *self.lengths.get(spine.index))
+        };
+        Blend::Lighten.apply(self.inner.spine_tip_color_at(spine, led), flash)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.inner.arc_color_at(arc, led)
+    }
+}
+
+impl<Decay, Inner, Levels, Lengths> Arrivals for Flash<Decay, Inner, Levels, Lengths>
+where
+    Decay: osc::Oscillator,
+    Inner: Pattern + Arrivals,
+    Levels: Storage<Value = u8>,
+    Lengths: Storage<Value = u8>,
+{
+    #[inline(always)]
+    fn arrived(&self, spine: Index) -> bool {
+        self.inner.arrived(spine)
+    }
+}
+
+/// White sparkles on random tips, lightened over the inner pattern's tips
+///
+/// - `chance` sets how likely a new sparkle is each tick, from never (-128) to always (127)
+/// - `decay` sets how quickly a sparkle fades, from 1 (-128) to 32 (127) brightness per tick
+pub struct Sparkle<Chance, Decay, Inner, Levels>
+where
+    Chance: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+    Levels: Storage<Value = u8>,
+{
+    pub chance: Chance,
+    pub decay: Decay,
+    pub inner: Inner,
+    levels: Levels,
+    spines: u8,
+}
+
+impl<Chance, Decay, Inner, Levels> Sparkle<Chance, Decay, Inner, Levels>
+where
+    Chance: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+    Levels: Storage<Value = u8>,
+{
+    pub fn new(
+        layout: &impl Layout,
+        chance: Chance,
+        decay: Decay,
+        inner: Inner,
+        levels: Levels,
+    ) -> Self {
+        check_levels("Sparkle", layout, &levels);
+        Self {
+            chance,
+            decay,
+            inner,
+            levels,
+            spines: layout.spines(),
+        }
+    }
+}
+
+impl<Chance, Decay, Inner, Levels> Pattern for Sparkle<Chance, Decay, Inner, Levels>
+where
+    Chance: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern,
+    Levels: Storage<Value = u8>,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.chance.tick();
+        self.decay.tick();
+        self.inner.tick();
+
+        let step = decay_step(self.decay.get());
+        for spine in 0..self.spines {
+            let level = self.levels.get_mut(spine);
+            *level = level.saturating_sub(step);
+        }
+
+        let chance = osc::to_u8(self.chance.get());
+        if chance > 0 && crate::rand::u8() <= chance {
+            let spine = crate::rand::below_u16(self.spines as u16) as u8;
+            self.levels.set(spine, 255);
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let sparkle = Hsv::new(0, 0, *self.levels.get(spine.index));
+        Blend::Lighten.apply(self.inner.spine_tip_color_at(spine, led), sparkle)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.inner.arc_color_at(arc, led)
+    }
+}
+
+impl<Chance, Decay, Inner, Levels> Arrivals for Sparkle<Chance, Decay, Inner, Levels>
+where
+    Chance: osc::Oscillator,
+    Decay: osc::Oscillator,
+    Inner: Pattern + Arrivals,
+    Levels: Storage<Value = u8>,
+{
+    #[inline(always)]
+    fn arrived(&self, spine: Index) -> bool {
+        self.inner.arrived(spine)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        osc::{Constant, OscillatorExt as _, sawtooth},
+        pattern::PerSpine,
+        star::FixedLayout,
+        streak::{StreakSpawner, StreakState},
+    };
+
+    const TIP: Index = Index::new(0, 1);
+
+    /// Spine 0 sends a streak out once every 256 ticks while spine 1 stays dark
+    fn spines(hue: u8) -> impl Pattern + Arrivals {
+        let streak = |spawner| {
+            StreakSpawner::new(
+                spawner,
+                Constant::<-120>, // 1 LED long
+                Constant::<127>,  // 2 LEDs per tick
+                Constant::<4>,
+                Hsv::new(hue, 255, 255),
+                [StreakState::default(); 8],
+            )
+        };
+        PerSpine::new([
+            streak(sawtooth().saturating_sub(126)),
+            streak(sawtooth().saturating_sub(127)),
+        ])
+    }
+
+    #[test]
+    fn test_flash_on_arrival() {
+        let mut flash = Flash::new(
+            &FixedLayout::new(2, 2, 4, 1, 2),
+            Constant::<0>,
+            spines(100),
+            [0u8; 2],
+            [0u8; 2],
+        );
+        let spine = |index| Index::new(index, 2);
+
+        let mut arrivals = 0;
+        let mut peak = Hsv::new(0, 0, 0);
+        for _ in 0..256 {
+            flash.tick();
+            if flash.arrived(spine(0)) {
+                arrivals += 1;
+                peak = flash.spine_tip_color_at(spine(0), TIP);
+            }
+            assert!(!flash.arrived(spine(1)));
+            assert_eq!(flash.spine_tip_color_at(spine(1), TIP).v, 0);
+        }
+        assert_eq!(arrivals, 1);
+        assert_eq!(peak, Hsv::new(100, 255, 255));
+
+        // The flash has faded by the time the next streak spawns
+        assert_eq!(flash.spine_tip_color_at(spine(0), TIP).v, 0);
+    }
+
+    #[test]
+    fn test_glow_follows_spine_hue() {
+        let glow = Glow::new(
+            &FixedLayout::new(2, 2, 4, 1, 2),
+            Constant::<0>,
+            spines(42),
+            [0u8; 2],
+        );
+        let color = glow.spine_tip_color_at(Index::new(1, 2), TIP);
+        assert_eq!(color, Hsv::new(42, 255, 128));
+    }
+
+    #[test]
+    fn test_glow_uneven_spines() {
+        /// Spine `n` is `4 + n` LEDs long
+        struct Uneven;
+
+        impl Layout for Uneven {
+            fn spines(&self) -> u8 {
+                2
+            }
+            fn arcs(&self) -> u8 {
+                2
+            }
+            fn leds(&self) -> u16 {
+                (4 + 5) * 2 + 2 * 2 + 2
+            }
+            fn spine_len_at(&self, index: u8) -> u8 {
+                4 + index
+            }
+            fn tip_len_at(&self, _index: u8) -> u8 {
+                1
+            }
+            fn arc_len_at(&self, _index: u8) -> u8 {
+                2
+            }
+        }
+
+        /// Hue counts up along each spine
+        struct Ramp;
+
+        impl Pattern for Ramp {
+            fn tick(&mut self) {}
+            fn spine_color_at(&self, _spine: Index, led: Index) -> Hsv {
+                Hsv::new(led.index, 255, 255)
+            }
+            fn spine_tip_color_at(&self, _spine: Index, _led: Index) -> Hsv {
+                Hsv::new(0, 0, 0)
+            }
+            fn arc_color_at(&self, _arc: Index, _led: Index) -> Hsv {
+                Hsv::new(0, 0, 0)
+            }
+        }
+
+        // Each tip takes the hue of the last LED of its own spine
+        let glow = Glow::new(&Uneven, Constant::<127>, Ramp, [0u8; 2]);
+        assert_eq!(glow.spine_tip_color_at(Index::new(0, 2), TIP).h, 3);
+        assert_eq!(glow.spine_tip_color_at(Index::new(1, 2), TIP).h, 4);
+    }
+
+    #[test]
+    fn test_sparkle() {
+        let black = Hsv::new(0, 0, 0);
+        let mut never = Sparkle::new(
+            &FixedLayout::new(2, 2, 4, 1, 2),
+            Constant::<{ i8::MIN }>,
+            Constant::<0>,
+            black,
+            [0u8; 2],
+        );
+        let mut always = Sparkle::new(
+            &FixedLayout::new(2, 2, 4, 1, 2),
+            Constant::<127>,
+            Constant::<0>,
+            black,
+            [0u8; 2],
+        );
+        never.tick();
+        always.tick();
+
+        fn lit(sparkle: &impl Pattern) -> usize {
+            (0..2)
+                .filter(|&spine| sparkle.spine_tip_color_at(Index::new(spine, 2), TIP).v > 0)
+                .count()
+        }
+        assert_eq!(lit(&never), 0);
+        assert_eq!(lit(&always), 1);
+        assert_eq!(always.spine_color_at(Index::new(0, 2), TIP), black);
+    }
+}
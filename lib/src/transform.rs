@@ -0,0 +1,586 @@
+use crate::{
+    color::Hsv,
+    osc,
+    pattern::{Index, Pattern, Seek},
+    star::Layout,
+    tip::Arrivals,
+};
+
+/// Moves an index by a signed number of steps, wrapping around its total
+#[inline(always)]
+fn shift(index: Index, steps: i16) -> Index {
+    if index.total == 0 {
+        return index;
+    }
+    let shifted = (index.index as i16 + steps).rem_euclid(index.total as i16);
+    Index::new(shifted as u8, index.total)
+}
+
+/// Flips an index so the first becomes the last
+#[inline(always)]
+fn flip(index: Index) -> Index {
+    if index.total == 0 {
+        return index;
+    }
+    Index::new(index.total - 1 - index.index, index.total)
+}
+
+/// Rotates the inner pattern across spines by a whole number of steps
+///
+/// Spine `n` (and the arc leaving it) shows what the inner pattern draws at `n + steps`. A
+/// constant gives a fixed orientation fix, while an oscillator steps the pattern around the star.
+pub struct Rotate<Steps, Inner>
+where
+    Steps: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub steps: Steps,
+    pub inner: Inner,
+}
+
+impl<Steps, Inner> Rotate<Steps, Inner>
+where
+    Steps: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub fn new(steps: Steps, inner: Inner) -> Self {
+        Self { steps, inner }
+    }
+}
+
+impl<Steps, Inner> Pattern for Rotate<Steps, Inner>
+where
+    Steps: osc::Oscillator,
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.steps.tick();
+        self.inner.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let spine = shift(spine, self.steps.get() as i16);
+        self.inner.spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let spine = shift(spine, self.steps.get() as i16);
+        self.inner.spine_tip_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let arc = shift(arc, self.steps.get() as i16);
+        self.inner.arc_color_at(arc, led)
+    }
+}
+
+impl<Steps, Inner> Arrivals for Rotate<Steps, Inner>
+where
+    Steps: osc::Oscillator,
+    Inner: Pattern + Arrivals,
+{
+    #[inline(always)]
+    fn arrived(&self, spine: Index) -> bool {
+        self.inner.arrived(shift(spine, self.steps.get() as i16))
+    }
+}
+
+/// Spins the inner pattern around the star
+///
+/// The `phase` oscillator is read as a fraction of a full revolution, so a sawtooth turns the
+/// pattern once every 256 ticks no matter how many spines there are.
+pub struct Spin<Phase, Inner>
+where
+    Phase: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub phase: Phase,
+    pub inner: Inner,
+}
+
+impl<Phase, Inner> Spin<Phase, Inner>
+where
+    Phase: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub fn new(phase: Phase, inner: Inner) -> Self {
+        Self { phase, inner }
+    }
+
+    #[inline(always)]
+    fn steps(&self, index: Index) -> i16 {
+        let phase = self.phase.get() as u8;
+        ((phase as u16 * index.total as u16) >> 8) as i16
+    }
+}
+
+impl<Phase, Inner> Pattern for Spin<Phase, Inner>
+where
+    Phase: osc::Oscillator,
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.phase.tick();
+        self.inner.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let spine = shift(spine, self.steps(spine));
+        self.inner.spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let spine = shift(spine, self.steps(spine));
+        self.inner.spine_tip_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let arc = shift(arc, self.steps(arc));
+        self.inner.arc_color_at(arc, led)
+    }
+}
+
+/// Mirrors the inner pattern across the axis through spine 0
+///
+/// Spine `n` shows spine `total - n`, and arcs are swapped and reversed to match, so
+/// clockwise motion becomes counter-clockwise.
+pub struct Mirror<Inner>
+where
+    Inner: Pattern,
+{
+    pub inner: Inner,
+}
+
+impl<Inner> Mirror<Inner>
+where
+    Inner: Pattern,
+{
+    pub fn new(inner: Inner) -> Self {
+        Self { inner }
+    }
+
+    #[inline(always)]
+    fn mirror(spine: Index) -> Index {
+        shift(flip(spine), 1)
+    }
+}
+
+impl<Inner> Pattern for Mirror<Inner>
+where
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.inner.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_color_at(Self::mirror(spine), led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_tip_color_at(Self::mirror(spine), led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        // The arc from n to n + 1 lands between total - n - 1 and total - n
+        self.inner.arc_color_at(flip(arc), flip(led))
+    }
+}
+
+/// Reverses the direction of the inner pattern along each spine
+///
+/// The LED at the center shows what the inner pattern draws at the tip end and vice versa. Arcs
+/// are passed through unchanged.
+pub struct Reverse<Inner>
+where
+    Inner: Pattern,
+{
+    pub inner: Inner,
+}
+
+impl<Inner> Reverse<Inner>
+where
+    Inner: Pattern,
+{
+    pub fn new(inner: Inner) -> Self {
+        Self { inner }
+    }
+}
+
+impl<Inner> Pattern for Reverse<Inner>
+where
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.inner.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_color_at(spine, flip(led))
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_tip_color_at(spine, flip(led))
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.inner.arc_color_at(arc, led)
+    }
+}
+
+/// Shifts the inner pattern along each spine by a number of LEDs, wrapping at the ends
+///
+/// Arcs are passed through unchanged.
+pub struct Offset<Leds, Inner>
+where
+    Leds: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub leds: Leds,
+    pub inner: Inner,
+}
+
+impl<Leds, Inner> Offset<Leds, Inner>
+where
+    Leds: osc::Oscillator,
+    Inner: Pattern,
+{
+    pub fn new(leds: Leds, inner: Inner) -> Self {
+        Self { leds, inner }
+    }
+}
+
+impl<Leds, Inner> Pattern for Offset<Leds, Inner>
+where
+    Leds: osc::Oscillator,
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.leds.tick();
+        self.inner.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let led = shift(led, self.leds.get() as i16);
+        self.inner.spine_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_tip_color_at(spine, led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        self.inner.arc_color_at(arc, led)
+    }
+}
+
+/// How the sectors of a [`Symmetric`] pattern relate to each other
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Every sector is a copy of the first
+    #[default]
+    Rotational,
+    /// Every other sector is a mirror image of the first
+    Mirror,
+}
+
+/// Draws one sector of spines and repeats it `N` times around the star
+///
+/// The inner pattern only sees the spines of a single sector, with `Index.total` set to the sector
+/// size, so a [`PerSpine`](crate::pattern::PerSpine) needs just one value per spine in a sector.
+/// With [`Symmetry::Mirror`], arcs that cross a mirror line show the arc that closes the sector.
+pub struct Symmetric<const N: u8, Inner>
+where
+    Inner: Pattern,
+{
+    pub symmetry: Symmetry,
+    pub inner: Inner,
+}
+
+impl<const N: u8, Inner> Symmetric<N, Inner>
+where
+    Inner: Pattern,
+{
+    pub fn new(layout: &impl Layout, symmetry: Symmetry, inner: Inner) -> Self {
+        assert!(
+            N > 0 && layout.spines() % N == 0 && layout.arcs() == layout.spines(),
+            "{N}-fold symmetry needs the {} spines and {} arcs to split into {N} equal sectors",
+            layout.spines(),
+            layout.arcs()
+        );
+        assert!(
+            symmetry == Symmetry::Rotational || N % 2 == 0,
+            "mirror symmetry needs an even number of sectors, not {N}"
+        );
+        Self { symmetry, inner }
+    }
+
+    /// Splits an index into whether its sector is mirrored, its position in the sector and the
+    /// sector size
+    #[inline(always)]
+    fn locate(&self, index: Index) -> (bool, u8, u8) {
+        let size = (index.total / N).max(1);
+        let sector = index.index / size;
+        let mirrored = self.symmetry == Symmetry::Mirror && sector % 2 == 1;
+        (mirrored, index.index % size, size)
+    }
+
+    #[inline(always)]
+    fn spine(&self, spine: Index) -> Index {
+        let (mirrored, local, size) = self.locate(spine);
+        if mirrored {
+            Index::new(size - 1 - local, size)
+        } else {
+            Index::new(local, size)
+        }
+    }
+}
+
+impl<const N: u8, Inner> Pattern for Symmetric<N, Inner>
+where
+    Inner: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        self.inner.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_color_at(self.spine(spine), led)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        self.inner.spine_tip_color_at(self.spine(spine), led)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let (mirrored, local, size) = self.locate(arc);
+        if !mirrored {
+            return self.inner.arc_color_at(Index::new(local, size), led);
+        }
+        // The arc from n to n + 1 lands between the mirror images of n + 1 and n
+        let arc = if local == size - 1 {
+            size - 1
+        } else {
+            size - 2 - local
+        };
+        self.inner.arc_color_at(Index::new(arc, size), flip(led))
+    }
+}
+
+impl<const N: u8, Inner: Seek> Seek for Symmetric<N, Inner> {
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.inner.seek(t);
+    }
+}
+
+impl<Steps, Inner> Seek for Rotate<Steps, Inner>
+where
+    Steps: osc::Seek,
+    Inner: Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.steps.seek(t);
+        self.inner.seek(t);
+    }
+}
+
+impl<Phase, Inner> Seek for Spin<Phase, Inner>
+where
+    Phase: osc::Seek,
+    Inner: Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.phase.seek(t);
+        self.inner.seek(t);
+    }
+}
+
+impl<Inner: Seek> Seek for Mirror<Inner> {
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.inner.seek(t);
+    }
+}
+
+impl<Inner: Seek> Seek for Reverse<Inner> {
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.inner.seek(t);
+    }
+}
+
+impl<Leds, Inner> Seek for Offset<Leds, Inner>
+where
+    Leds: osc::Seek,
+    Inner: Seek,
+{
+    #[inline(always)]
+    fn seek(&mut self, t: u32) {
+        self.leds.seek(t);
+        self.inner.seek(t);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        osc::{Constant, sawtooth},
+        pattern::PerSpine,
+        star::FixedLayout,
+    };
+
+    // Encodes the position it was asked for in the color
+    struct Probe;
+
+    impl Pattern for Probe {
+        fn tick(&mut self) {}
+
+        fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+            Hsv::new(spine.index, led.index, 255)
+        }
+
+        fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+            Hsv::new(spine.index, led.index, 128)
+        }
+
+        fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+            Hsv::new(arc.index, led.index, 64)
+        }
+    }
+
+    fn spine(index: u8) -> Index {
+        Index::new(index, 12)
+    }
+
+    fn led(index: u8) -> Index {
+        Index::new(index, 5)
+    }
+
+    #[test]
+    fn test_rotate() {
+        let pattern = Rotate::new(Constant::<5>, Probe);
+        assert_eq!(pattern.spine_color_at(spine(0), led(1)).h, 5);
+        assert_eq!(pattern.spine_color_at(spine(8), led(1)).h, 1);
+        assert_eq!(pattern.arc_color_at(spine(11), led(1)).h, 4);
+
+        let pattern = Rotate::new(Constant::<-1>, Probe);
+        assert_eq!(pattern.spine_color_at(spine(0), led(1)).h, 11);
+        assert_eq!(pattern.spine_tip_color_at(spine(3), led(0)).h, 2);
+    }
+
+    #[test]
+    fn test_spin() {
+        let mut pattern = Spin::new(sawtooth(), Probe);
+        assert_eq!(pattern.spine_color_at(spine(0), led(0)).h, 0);
+
+        // Half a revolution
+        for _ in 0..128 {
+            pattern.tick();
+        }
+        assert_eq!(pattern.spine_color_at(spine(0), led(0)).h, 6);
+        assert_eq!(pattern.arc_color_at(spine(7), led(0)).h, 1);
+    }
+
+    #[test]
+    fn test_mirror() {
+        let pattern = Mirror::new(Probe);
+        assert_eq!(pattern.spine_color_at(spine(0), led(2)).h, 0);
+        assert_eq!(pattern.spine_color_at(spine(1), led(2)).h, 11);
+        assert_eq!(pattern.spine_color_at(spine(6), led(2)).h, 6);
+
+        // Arc 0 (spines 0 -> 1) becomes arc 11 (spines 11 -> 0), reversed
+        assert_eq!(pattern.arc_color_at(spine(0), led(0)), Hsv::new(11, 4, 64));
+    }
+
+    #[test]
+    fn test_symmetric() {
+        // Three sectors share four per-spine values
+        let values = core::array::from_fn::<_, 4, _>(|v| Hsv::new(v as u8, 0, 255));
+        let pattern = Symmetric::<3, _>::new(
+            &FixedLayout::new(12, 12, 5, 1, 5),
+            Symmetry::Rotational,
+            PerSpine::new(values),
+        );
+        let hues: Vec<u8> = (0..12)
+            .map(|index| pattern.spine_color_at(spine(index), led(0)).h)
+            .collect();
+        assert_eq!(hues, [0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3]);
+
+        let pattern =
+            Symmetric::<4, _>::new(&FixedLayout::new(12, 12, 5, 1, 5), Symmetry::Mirror, Probe);
+        let hues: Vec<u8> = (0..12)
+            .map(|index| pattern.spine_tip_color_at(spine(index), led(0)).h)
+            .collect();
+        assert_eq!(hues, [0, 1, 2, 2, 1, 0, 0, 1, 2, 2, 1, 0]);
+
+        // Arcs inside a mirrored sector are swapped and reversed
+        assert_eq!(pattern.arc_color_at(spine(1), led(0)), Hsv::new(1, 0, 64));
+        assert_eq!(pattern.arc_color_at(spine(3), led(0)), Hsv::new(1, 4, 64));
+        // Arcs crossing a mirror line close the sector
+        assert_eq!(pattern.arc_color_at(spine(2), led(0)), Hsv::new(2, 0, 64));
+        assert_eq!(pattern.arc_color_at(spine(5), led(0)), Hsv::new(2, 4, 64));
+    }
+
+    #[test]
+    #[should_panic(expected = "mirror symmetry needs an even number of sectors, not 3")]
+    fn test_symmetric_odd_mirror() {
+        Symmetric::<3, _>::new(&FixedLayout::new(12, 12, 5, 1, 5), Symmetry::Mirror, Probe);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let pattern = Reverse::new(Probe);
+        assert_eq!(
+            pattern.spine_color_at(spine(3), led(0)),
+            Hsv::new(3, 4, 255)
+        );
+        assert_eq!(
+            pattern.spine_color_at(spine(3), led(4)),
+            Hsv::new(3, 0, 255)
+        );
+        assert_eq!(pattern.arc_color_at(spine(3), led(0)), Hsv::new(3, 0, 64));
+
+        // Empty strips are left alone
+        let empty = Index::new(0, 0);
+        assert_eq!(flip(empty).index, 0);
+    }
+
+    #[test]
+    fn test_offset() {
+        let pattern = Offset::new(Constant::<2>, Probe);
+        assert_eq!(pattern.spine_color_at(spine(0), led(0)).s, 2);
+        assert_eq!(pattern.spine_color_at(spine(0), led(4)).s, 1);
+        assert_eq!(pattern.arc_color_at(spine(0), led(0)).s, 0);
+
+        let pattern = Offset::new(Constant::<-1>, Probe);
+        assert_eq!(pattern.spine_color_at(spine(0), led(0)).s, 4);
+    }
+}
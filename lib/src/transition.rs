@@ -0,0 +1,310 @@
+use crate::{
+    color::{Hsv, scale8},
+    pattern::{Index, Pattern, Surface},
+};
+
+/// Width of the soft edge on wipes, in 0..255 position units
+const WIPE_EDGE: u16 = 32;
+
+/// How the outgoing pattern gives way to the incoming one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Style {
+    /// Blend every LED from the outgoing color to the incoming color
+    #[default]
+    Crossfade,
+    /// Sweep the incoming pattern outward along every spine, starting at the center
+    ///
+    /// Arcs switch first and tips switch last.
+    WipeOut,
+    /// Sweep the incoming pattern around the star, from spine 0 to the last spine
+    RotateWipe,
+    /// Fade the outgoing pattern to black, then fade the incoming pattern up from black
+    FadeThroughBlack,
+}
+
+impl Style {
+    /// Mix the outgoing and incoming colors of an LED
+    ///
+    /// `progress` runs from 0 (all outgoing) to 255 (all incoming).
+    #[inline]
+    pub fn mix(
+        self,
+        progress: u8,
+        surface: Surface,
+        spine: Index,
+        led: Index,
+        from: Hsv,
+        to: Hsv,
+    ) -> Hsv {
+        match self {
+            Style::Crossfade => from.lerp(to, progress),
+            Style::WipeOut => {
+                let position = match surface {
+                    Surface::Spine => led.fraction(),
+                    Surface::Tip => u8::MAX,
+                    Surface::Arc => 0,
+                };
+                from.lerp(to, wipe(progress, position))
+            }
+            Style::RotateWipe => {
+                let position = if spine.total == 0 {
+                    0
+                } else {
+                    (spine.index as u16 * 255 / spine.total as u16) as u8
+                };
+                from.lerp(to, wipe(progress, position))
+            }
+            Style::FadeThroughBlack => {
+                if progress < 128 {
+                    let mut color = from;
+                    color.v = scale8(color.v, 255 - progress * 2);
+                    color
+                } else {
+                    let mut color = to;
+                    color.v = scale8(color.v, (progress - 128) * 2 + 1);
+                    color
+                }
+            }
+        }
+    }
+}
+
+/// Returns how far an LED at `position` is into the incoming pattern
+#[inline(always)]
+fn wipe(progress: u8, position: u8) -> u8 {
+    // Stretch the progress so the soft edge fully clears the last position
+    let front = (progress as u32 * (256 + WIPE_EDGE as u32) / 255) as u16;
+    let Some(distance) = front.checked_sub(position as u16) else {
+        return 0;
+    };
+    if distance >= WIPE_EDGE {
+        return 255;
+    }
+    (distance * 255 / WIPE_EDGE) as u8
+}
+
+/// Blends from one pattern to another over a fixed number of ticks
+///
+/// Both patterns are ticked while the transition runs. Once it completes, only the incoming
+/// pattern is ticked and rendered, and it can be taken back out with [`Transition::into_inner`].
+pub struct Transition<From, To>
+where
+    From: Pattern,
+    To: Pattern,
+{
+    pub from: From,
+    pub to: To,
+    pub style: Style,
+    duration: u16,
+    elapsed: u16,
+}
+
+impl<From, To> Transition<From, To>
+where
+    From: Pattern,
+    To: Pattern,
+{
+    pub fn new(from: From, to: To, style: Style, duration: u16) -> Self {
+        Self {
+            from,
+            to,
+            style,
+            duration,
+            elapsed: 0,
+        }
+    }
+
+    /// How far through the transition we are, from 0 to 255
+    #[inline(always)]
+    pub fn progress(&self) -> u8 {
+        if self.is_done() {
+            return u8::MAX;
+        }
+        (self.elapsed as u32 * 255 / self.duration as u32) as u8
+    }
+
+    #[inline(always)]
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Returns the incoming pattern
+    pub fn into_inner(self) -> To {
+        self.to
+    }
+
+    #[inline(always)]
+    fn mix(&self, surface: Surface, spine: Index, led: Index, from: Hsv, to: Hsv) -> Hsv {
+        self.style
+            .mix(self.progress(), surface, spine, led, from, to)
+    }
+}
+
+impl<From, To> Pattern for Transition<From, To>
+where
+    From: Pattern,
+    To: Pattern,
+{
+    #[inline(always)]
+    fn tick(&mut self) {
+        if !self.is_done() {
+            self.from.tick();
+            self.elapsed += 1;
+        }
+        self.to.tick();
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let to = self.to.spine_color_at(spine, led);
+        if self.is_done() {
+            return to;
+        }
+        let from = self.from.spine_color_at(spine, led);
+        self.mix(Surface::Spine, spine, led, from, to)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let to = self.to.spine_tip_color_at(spine, led);
+        if self.is_done() {
+            return to;
+        }
+        let from = self.from.spine_tip_color_at(spine, led);
+        self.mix(Surface::Tip, spine, led, from, to)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let to = self.to.arc_color_at(arc, led);
+        if self.is_done() {
+            return to;
+        }
+        let from = self.from.arc_color_at(arc, led);
+        self.mix(Surface::Arc, arc, led, from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        osc::{Constant, Oscillator as _, sawtooth},
+        pattern::Oscillator,
+    };
+
+    const BLACK: Hsv = Hsv::new(0, 0, 0);
+    const WHITE: Hsv = Hsv::new(0, 0, 255);
+
+    fn run(transition: &mut Transition<Hsv, Hsv>, ticks: u16) {
+        for _ in 0..ticks {
+            transition.tick();
+        }
+    }
+
+    #[test]
+    fn test_crossfade() {
+        let mut transition = Transition::new(BLACK, WHITE, Style::Crossfade, 10);
+        let spine = Index::new(0, 1);
+        let led = Index::new(0, 1);
+
+        assert_eq!(transition.spine_color_at(spine, led), BLACK);
+        run(&mut transition, 5);
+        assert_eq!(transition.spine_color_at(spine, led).v, 127);
+        run(&mut transition, 5);
+        assert!(transition.is_done());
+        assert_eq!(transition.spine_color_at(spine, led), WHITE);
+    }
+
+    #[test]
+    fn test_wipe_out() {
+        let mut transition = Transition::new(BLACK, WHITE, Style::WipeOut, 100);
+        let spine = Index::new(0, 1);
+        let center = Index::new(0, 10);
+        let tip = Index::new(9, 10);
+
+        run(&mut transition, 50);
+
+        // The center has switched over while the tip hasn't been reached yet
+        assert_eq!(transition.spine_color_at(spine, center), WHITE);
+        assert_eq!(transition.arc_color_at(spine, center), WHITE);
+        assert_eq!(transition.spine_color_at(spine, tip), BLACK);
+        assert_eq!(transition.spine_tip_color_at(spine, tip), BLACK);
+
+        run(&mut transition, 50);
+        assert_eq!(transition.spine_tip_color_at(spine, tip), WHITE);
+    }
+
+    #[test]
+    fn test_rotate_wipe() {
+        let mut transition = Transition::new(BLACK, WHITE, Style::RotateWipe, 100);
+        let led = Index::new(0, 1);
+
+        run(&mut transition, 50);
+
+        let first = transition.spine_color_at(Index::new(0, 12), led);
+        let last = transition.spine_color_at(Index::new(11, 12), led);
+        assert_eq!(first, WHITE);
+        assert_eq!(last, BLACK);
+    }
+
+    #[test]
+    fn test_wipe_edge() {
+        // Positions just behind the front are partially blended
+        assert_eq!(wipe(0, 0), 0);
+        assert_eq!(wipe(255, 255), 255);
+        let edge = wipe(128, 128);
+        assert!(edge > 0 && edge < 255, "{edge}");
+    }
+
+    #[test]
+    fn test_fade_through_black() {
+        let from = Hsv::new(10, 255, 255);
+        let to = Hsv::new(100, 255, 255);
+        let mut transition = Transition::new(from, to, Style::FadeThroughBlack, 100);
+        let spine = Index::new(0, 1);
+        let led = Index::new(0, 1);
+
+        run(&mut transition, 25);
+        let color = transition.spine_color_at(spine, led);
+        assert_eq!(color.h, from.h);
+        assert!(color.v < from.v);
+
+        run(&mut transition, 25);
+        assert!(transition.spine_color_at(spine, led).v < 2);
+
+        run(&mut transition, 25);
+        let color = transition.spine_color_at(spine, led);
+        assert_eq!(color.h, to.h);
+        assert!(color.v > 0 && color.v < to.v);
+    }
+
+    #[test]
+    fn test_ticks_both_until_done() {
+        let osc = || Oscillator {
+            h: sawtooth(),
+            s: Constant::<0>,
+            v: Constant::<0>,
+        };
+        let mut transition = Transition::new(osc(), osc(), Style::Crossfade, 4);
+
+        for _ in 0..10 {
+            transition.tick();
+        }
+
+        // The outgoing pattern stops ticking once the transition is done
+        assert_eq!(transition.from.h.get(), 4);
+        let to = transition.into_inner();
+        assert_eq!(to.h.get(), 10);
+    }
+
+    #[test]
+    fn test_zero_duration() {
+        let transition = Transition::new(BLACK, WHITE, Style::Crossfade, 0);
+        assert!(transition.is_done());
+        assert_eq!(
+            transition.spine_color_at(Index::new(0, 1), Index::new(0, 1)),
+            WHITE
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{
+    color::{Hsv, scale8},
+    osc,
+    pattern::{Index, Pattern},
+    star::{Cells, Layout},
+    storage::Storage,
+};
+
+/// Brightest level a twinkle starts at
+const MAX_LEVEL: u8 = 0x0F;
+
+/// Random LEDs light up and fade out independently
+///
+/// Each LED's fade state is a 4-bit level, packed two to a byte, so the ~480 logical LEDs of the
+/// default layout need 240 bytes of `levels` storage. The inner pattern provides the color, scaled
+/// by each LED's level.
+///
+/// - `density` sets how many twinkles start each tick, from none (-128) up to ~4 (127)
+/// - `fade_rate` sets how quickly they fade, from never (-128) up to ~4 levels per tick (127)
+pub struct Twinkle<Density, FadeRate, Inner, Levels>
+where
+    Density: osc::Oscillator,
+    FadeRate: osc::Oscillator,
+    Inner: Pattern,
+    Levels: Storage<Value = u8>,
+{
+    pub density: Density,
+    pub fade_rate: FadeRate,
+    pub inner: Inner,
+    cells: Cells,
+    levels: Levels,
+    spawn_frac: u8,
+    fade_frac: u8,
+}
+
+impl<Density, FadeRate, Inner, Levels> Twinkle<Density, FadeRate, Inner, Levels>
+where
+    Density: osc::Oscillator,
+    FadeRate: osc::Oscillator,
+    Inner: Pattern,
+    Levels: Storage<Value = u8>,
+{
+    pub fn new(
+        layout: &impl Layout,
+        density: Density,
+        fade_rate: FadeRate,
+        inner: Inner,
+        levels: Levels,
+    ) -> Self {
+        let cells = Cells::new(layout);
+        let needed = cells.len().div_ceil(2);
+        assert!(
+            levels.len() as u16 >= needed,
+            "Twinkle needs {needed} bytes of level storage for {} LEDs, but only {} were provided",
+            cells.len(),
+            levels.len()
+        );
+
+        Self {
+            density,
+            fade_rate,
+            inner,
+            cells,
+            levels,
+            spawn_frac: 0,
+            fade_frac: 0,
+        }
+    }
+
+    /// Returns the 4-bit level of a cell
+    #[inline(always)]
+    fn level(&self, cell: u16) -> u8 {
+        let byte = *self.levels.get((cell / 2) as u8);
+        if cell % 2 == 0 {
+            byte & 0x0F
+        } else {
+            byte >> 4
+        }
+    }
+
+    #[inline(always)]
+    fn light(&mut self, cell: u16) {
+        let byte = self.levels.get_mut((cell / 2) as u8);
+        if cell % 2 == 0 {
+            *byte |= MAX_LEVEL;
+        } else {
+            *byte |= MAX_LEVEL << 4;
+        }
+    }
+
+    fn fade(&mut self, steps: u8) {
+        for byte in self.levels.iter_mut() {
+            let low = (*byte & 0x0F).saturating_sub(steps);
+            let high = (*byte >> 4).saturating_sub(steps);
+            *byte = (high << 4) | low;
+        }
+    }
+
+    /// Accumulates a rate in 2.6 fixed-point, returning the whole steps to take this tick
+    #[inline(always)]
+    fn accumulate(frac: &mut u8, rate: osc::Value) -> u8 {
+        let accumulated = *frac as u16 + osc::to_u8(rate) as u16;
+        *frac = (accumulated & 0x3F) as u8;
+        (accumulated >> 6) as u8
+    }
+
+    #[inline(always)]
+    fn color(&self, mut color: Hsv, cell: u16) -> Hsv {
+        // Spread the 4-bit level across the full brightness range
+        color.v = scale8(color.v, self.level(cell) * 17);
+        color
+    }
+}
+
+impl<Density, FadeRate, Inner, Levels> Pattern for Twinkle<Density, FadeRate, Inner, Levels>
+where
+    Density: osc::Oscillator,
+    FadeRate: osc::Oscillator,
+    Inner: Pattern,
+    Levels: Storage<Value = u8>,
+{
+    fn tick(&mut self) {
+        self.density.tick();
+        self.fade_rate.tick();
+        self.inner.tick();
+
+        let steps = Self::accumulate(&mut self.fade_frac, self.fade_rate.get());
+        if steps > 0 {
+            self.fade(steps);
+        }
+
+        let spawns = Self::accumulate(&mut self.spawn_frac, self.density.get());
+        for _ in 0..spawns {
+            let cell = crate::rand::below_u16(self.cells.len());
+            self.light(cell);
+        }
+    }
+
+    #[inline(always)]
+    fn spine_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let cell = self.cells.spine(spine, led);
+        self.color(self.inner.spine_color_at(spine, led), cell)
+    }
+
+    #[inline(always)]
+    fn spine_tip_color_at(&self, spine: Index, led: Index) -> Hsv {
+        let cell = self.cells.tip(spine, led);
+        self.color(self.inner.spine_tip_color_at(spine, led), cell)
+    }
+
+    #[inline(always)]
+    fn arc_color_at(&self, arc: Index, led: Index) -> Hsv {
+        let cell = self.cells.arc(arc, led);
+        self.color(self.inner.arc_color_at(arc, led), cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{osc::Constant, star::FixedLayout};
+
+    const WHITE: Hsv = Hsv::new(0, 0, 255);
+
+    fn lit<D, F>(twinkle: &Twinkle<D, F, Hsv, [u8; 18]>) -> Vec<u8>
+    where
+        D: osc::Oscillator,
+        F: osc::Oscillator,
+    {
+        let mut values = vec![];
+        for spine in 0..4 {
+            let spine = Index::new(spine, 4);
+            for led in 0..6 {
+                values.push(twinkle.spine_color_at(spine, Index::new(led, 6)).v);
+            }
+            for led in 0..2 {
+                values.push(twinkle.arc_color_at(spine, Index::new(led, 2)).v);
+            }
+            values.push(twinkle.spine_tip_color_at(spine, Index::new(0, 1)).v);
+        }
+        values
+    }
+
+    #[test]
+    fn test_twinkles_light_and_fade() {
+        crate::rand::seed(5);
+        let mut twinkle = Twinkle::new(
+            &FixedLayout::new(4, 4, 6, 1, 2),
+            Constant::<127>,
+            Constant::<{ i8::MIN }>,
+            WHITE,
+            [0u8; 18],
+        );
+
+        for _ in 0..4 {
+            twinkle.tick();
+        }
+        let count = lit(&twinkle).iter().filter(|v| **v > 0).count();
+        assert!(count > 4, "only {count} LEDs lit");
+
+        // Stop spawning and fade everything out
+        let mut twinkle = Twinkle {
+            density: Constant::<{ i8::MIN }>,
+            fade_rate: Constant::<127>,
+            inner: twinkle.inner,
+            cells: twinkle.cells,
+            levels: twinkle.levels,
+            spawn_frac: 0,
+            fade_frac: 0,
+        };
+        twinkle.tick();
+        let dimmer = lit(&twinkle).into_iter().max().unwrap();
+        assert!(dimmer > 0 && dimmer < 254, "{dimmer}");
+
+        for _ in 0..4 {
+            twinkle.tick();
+        }
+        assert!(lit(&twinkle).iter().all(|v| *v == 0));
+    }
+
+    #[test]
+    fn test_no_density() {
+        let mut twinkle = Twinkle::new(
+            &FixedLayout::new(4, 4, 6, 1, 2),
+            Constant::<{ i8::MIN }>,
+            Constant::<0>,
+            WHITE,
+            [0u8; 18],
+        );
+
+        for _ in 0..100 {
+            twinkle.tick();
+        }
+        assert!(lit(&twinkle).iter().all(|v| *v == 0));
+    }
+
+    #[test]
+    fn test_packed_levels() {
+        let mut twinkle = Twinkle::new(
+            &FixedLayout::new(4, 4, 6, 1, 2),
+            Constant::<{ i8::MIN }>,
+            Constant::<{ i8::MIN }>,
+            WHITE,
+            [0u8; 18],
+        );
+
+        // Neighboring cells share a byte but keep independent levels
+        twinkle.light(3);
+        assert_eq!(twinkle.level(2), 0);
+        assert_eq!(twinkle.level(3), MAX_LEVEL);
+        twinkle.fade(5);
+        twinkle.light(2);
+        assert_eq!(twinkle.level(2), MAX_LEVEL);
+        assert_eq!(twinkle.level(3), MAX_LEVEL - 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Twinkle needs 18 bytes of level storage for 36 LEDs")]
+    fn test_storage_too_small() {
+        Twinkle::new(
+            &FixedLayout::new(4, 4, 6, 1, 2),
+            Constant::<0>,
+            Constant::<0>,
+            WHITE,
+            [0u8; 17],
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use led_star::{
-    config,
-    pattern::Pattern,
+    param::{Spec, Tunable},
+    registry::{self, Instance, MaybeSeek},
     star::{Layout, Star},
 };
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 
 // Set up panic hook for better error messages in browser console
@@ -11,7 +12,7 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
-struct State<L: Layout, P: Pattern> {
+struct State<L: Layout, P: MaybeSeek> {
     star: Star<L, P>,
 }
 
@@ -25,10 +26,12 @@ trait StateI {
     fn leds(&self) -> u16;
 
     fn tick(&mut self);
+    fn seekable(&self) -> bool;
+    fn seek(&mut self, t: u32);
     fn fill(&self, buf: &mut [u8]) -> Result<(), &'static str>;
 }
 
-impl<L: Layout, P: Pattern> StateI for State<L, P> {
+impl<L: Layout, P: MaybeSeek> StateI for State<L, P> {
     fn spines(&self) -> u8 {
         self.star.layout.spines()
     }
@@ -53,6 +56,14 @@ impl<L: Layout, P: Pattern> StateI for State<L, P> {
         self.star.tick();
     }
 
+    fn seekable(&self) -> bool {
+        self.star.pattern.seekable()
+    }
+
+    fn seek(&mut self, t: u32) {
+        self.star.pattern.seek_to(t);
+    }
+
     fn fill(&self, buffer: &mut [u8]) -> Result<(), &'static str> {
         if buffer.len() < self.star.layout.leds() as usize * 3 {
             return Err("buffer is too small");
@@ -75,17 +86,31 @@ impl<L: Layout, P: Pattern> StateI for State<L, P> {
 #[wasm_bindgen]
 pub struct Visualizer {
     state: Box<dyn StateI>,
+    params: Rc<dyn Tunable>,
+}
+
+impl Visualizer {
+    fn from_instance(instance: Instance) -> Self {
+        let star = Star::new(instance.layout, instance.pattern);
+        let state = Box::new(State { star });
+
+        Self {
+            state,
+            params: instance.params,
+        }
+    }
+
+    fn spec(&self, index: u8) -> Result<&Spec, JsValue> {
+        self.params
+            .specs()
+            .get(index as usize)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown parameter index: {index}")))
+    }
 }
 
 impl Default for Visualizer {
     fn default() -> Self {
-        let layout = config::layout();
-        let pattern = config::pattern();
-        let star = Star::new(layout, pattern);
-        let state = State { star };
-        let state = Box::new(state);
-
-        Self { state }
+        Self::from_instance(registry::PATTERNS[0].create())
     }
 }
 
@@ -105,17 +130,67 @@ impl Visualizer {
         self.state.tick();
     }
 
-    pub fn set_pattern(&mut self, _pattern: &str) -> Result<(), JsValue> {
-        // match pattern.parse() {
-        //     Ok(pattern) => {
-        //         self.star.pattern = pattern;
-        //         Ok(())
-        //     }
-        //     Err(_) => Err(JsValue::from_str("Invalid pattern")),
-        // }
+    /// Whether the current pattern can jump to any frame with `seek`
+    pub fn seekable(&self) -> bool {
+        self.state.seekable()
+    }
+
+    /// Jump to `t` ticks after the pattern started, forwards or backwards
+    pub fn seek(&mut self, t: u32) -> Result<(), JsValue> {
+        if !self.state.seekable() {
+            return Err(JsValue::from_str("The current pattern can't seek"));
+        }
+        self.state.seek(t);
         Ok(())
     }
 
+    /// Switch to one of the patterns from `get_available_patterns`, restarting the animation
+    pub fn set_pattern(&mut self, pattern: &str) -> Result<(), JsValue> {
+        let Some(instance) = registry::create(pattern) else {
+            return Err(JsValue::from_str(&format!("Unknown pattern: {pattern}")));
+        };
+        *self = Self::from_instance(instance);
+        Ok(())
+    }
+
+    /// Get the number of tunable parameters on the current pattern
+    pub fn param_count(&self) -> u8 {
+        self.params.specs().len() as u8
+    }
+
+    /// Get the name of a parameter
+    pub fn param_name(&self, index: u8) -> Result<String, JsValue> {
+        Ok(self.spec(index)?.name.to_string())
+    }
+
+    /// Get the lowest value a parameter accepts
+    pub fn param_min(&self, index: u8) -> Result<i8, JsValue> {
+        Ok(self.spec(index)?.min)
+    }
+
+    /// Get the highest value a parameter accepts
+    pub fn param_max(&self, index: u8) -> Result<i8, JsValue> {
+        Ok(self.spec(index)?.max)
+    }
+
+    /// Get the default value of a parameter
+    pub fn param_default(&self, index: u8) -> Result<i8, JsValue> {
+        Ok(self.spec(index)?.default)
+    }
+
+    /// Get the current value of a parameter
+    pub fn param_value(&self, index: u8) -> Result<i8, JsValue> {
+        self.spec(index)?;
+        Ok(self.params.value_at(index))
+    }
+
+    /// Set a parameter by name, returning the value after clamping it to the parameter's range
+    pub fn set_param(&mut self, name: &str, value: i8) -> Result<i8, JsValue> {
+        self.params
+            .set(name, value)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown parameter: {name}")))
+    }
+
     /// Write LED colors directly into the provided buffer (h, s, v, h, s, v, ...)
     /// The buffer must be at least total_leds() * 3 bytes
     pub fn read_leds_into(&self, buffer: &mut [u8]) -> Result<(), JsValue> {
@@ -151,9 +226,5 @@ impl Visualizer {
 /// Get all available patterns
 #[wasm_bindgen]
 pub fn get_available_patterns() -> Vec<JsValue> {
-    // DynamicPattern::NAMES
-    //     .into_iter()
-    //     .map(|p| JsValue::from(*p))
-    //     .collect()
-    vec![JsValue::from("Classic")]
+    registry::names().map(JsValue::from).collect()
 }
@@ -42,14 +42,7 @@ class AnimationManager {
     if (this.visualizer) return;
 
     this.visualizer = (await getVisualizer()) as unknown as Visualizer;
-    const totalLeds = this.visualizer.total_leds();
-    this.ledBuffer = new Uint8Array(totalLeds * 3);
-
-    // Pre-allocate HSV objects
-    this.leds = new Array(totalLeds);
-    for (let i = 0; i < totalLeds; i++) {
-      this.leds[i] = { h: 0, s: 0, v: 0 };
-    }
+    this.allocateLeds();
 
     this.running = true;
     this.animationState = this.getCurrentState();
@@ -73,6 +66,8 @@ class AnimationManager {
   async setPattern(pattern: string) {
     if (!this.visualizer) await this.initialize();
     this.visualizer!.set_pattern(pattern);
+    // Some patterns use a layout with more LEDs, such as one with tips
+    this.allocateLeds();
 
     const state = this.getCurrentState();
     this.animationState = state;
@@ -129,6 +124,18 @@ class AnimationManager {
     }, 1000 / this.speed);
   };
 
+  private allocateLeds() {
+    const totalLeds = this.visualizer!.total_leds();
+    if (this.ledBuffer?.length === totalLeds * 3) return;
+    this.ledBuffer = new Uint8Array(totalLeds * 3);
+
+    // Pre-allocate HSV objects
+    this.leds = new Array(totalLeds);
+    for (let i = 0; i < totalLeds; i++) {
+      this.leds[i] = { h: 0, s: 0, v: 0 };
+    }
+  }
+
   private getCurrentState(): AnimationState {
     return {
       leds: this.leds,